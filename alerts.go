@@ -0,0 +1,69 @@
+package main
+
+import (
+	"fmt"
+	"log/slog"
+	"os"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+var (
+	// AlertRoleID is pinged when the queue stalls one player short of a
+	// full stack. Alerts are disabled when unset.
+	AlertRoleID = os.Getenv("STANDBY_ALERT_ROLE_ID")
+
+	// AlertAfter is how long the queue may sit one player short before
+	// AlertRoleID is pinged, for presets that don't set their own.
+	AlertAfter = durationEnv("STANDBY_ALERT_AFTER", 20*time.Minute)
+)
+
+// updateAlertLocked arms the stall alert when the queue is one player short
+// of a stack and disarms it otherwise. An alert fires at most once per stall.
+//
+// lock must be held
+func (q *queueState) updateAlertLocked(s *discordgo.Session) {
+	if AlertRoleID == "" {
+		return
+	}
+	if q.currentMsgID == "" || len(q.users) != q.preset.Size-1 {
+		q.stopAlertLocked()
+		return
+	}
+	if q.alertTimer != nil {
+		return
+	}
+
+	var t *time.Timer
+	t = time.AfterFunc(q.preset.AlertAfter, func() {
+		q.Lock()
+		defer q.Unlock()
+
+		if q.alertTimer != t {
+			return
+		}
+		content := tr("<@&%s> the queue has been at %d/%d for %s. One more player is needed!",
+			AlertRoleID, len(q.users), q.preset.Size, q.preset.AlertAfter)
+		if tmpl, ok := q.templates[templateOneMore]; ok {
+			content = renderTemplate(tmpl, q.queueNameLocked(), q.preset.Size-len(q.users), fmt.Sprintf("<@&%s>", AlertRoleID))
+		}
+		if _, err := sendMessage(s, q.channelLocked(), &discordgo.MessageSend{
+			Content: content,
+			AllowedMentions: &discordgo.MessageAllowedMentions{
+				Roles: []string{AlertRoleID},
+			},
+		}); err != nil {
+			slog.Error("error sending stall alert", "err", err)
+		}
+	})
+	q.alertTimer = t
+}
+
+// lock must be held
+func (q *queueState) stopAlertLocked() {
+	if q.alertTimer != nil {
+		q.alertTimer.Stop()
+		q.alertTimer = nil
+	}
+}
@@ -0,0 +1,40 @@
+package main
+
+import (
+	"log/slog"
+	"os"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// AnnounceChannelID is an optional channel told when a queue opens and when
+// it fills. The announcements are deleted when the queue closes.
+var AnnounceChannelID = os.Getenv("STANDBY_ANNOUNCE_CHANNEL_ID")
+
+// announceLocked posts a short announcement about the current queue.
+//
+// lock must be held
+func (q *queueState) announceLocked(s *discordgo.Session, content string) {
+	if AnnounceChannelID == "" {
+		return
+	}
+	m, err := sendMessage(s, AnnounceChannelID, &discordgo.MessageSend{
+		Content:         content,
+		AllowedMentions: &discordgo.MessageAllowedMentions{},
+	})
+	if err != nil {
+		slog.Error("error sending announcement", "err", err)
+		return
+	}
+	q.announceMsgIDs = append(q.announceMsgIDs, m.ID)
+}
+
+// lock must be held
+func (q *queueState) clearAnnouncementsLocked(s *discordgo.Session) {
+	for _, msgID := range q.announceMsgIDs {
+		if err := deleteMessage(s, AnnounceChannelID, msgID); err != nil {
+			slog.Error("error deleting announcement", "err", err, "message", msgID)
+		}
+	}
+	q.announceMsgIDs = nil
+}
@@ -0,0 +1,138 @@
+package main
+
+import (
+	"crypto/subtle"
+	"encoding/json"
+	"log/slog"
+	"net/http"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// APIToken is the bearer token required by the REST API. The API is
+// disabled when it is unset.
+var APIToken = os.Getenv("STANDBY_API_TOKEN")
+
+type apiUser struct {
+	ID       string     `json:"id"`
+	Username string     `json:"username"`
+	Alias    string     `json:"alias,omitempty"`
+	JoinedAt *time.Time `json:"joined_at,omitempty"`
+}
+
+type apiQueue struct {
+	Open      bool          `json:"open"`
+	MessageID string        `json:"message_id,omitempty"`
+	Name      string        `json:"name,omitempty"`
+	Size      int           `json:"size,omitempty"`
+	OpenedAt  *time.Time    `json:"opened_at,omitempty"`
+	Details   *queueDetails `json:"details,omitempty"`
+	Queue     []apiUser     `json:"queue"`
+	Waitlist  []apiUser     `json:"waitlist"`
+}
+
+func (q *queueState) apiHandler(s *discordgo.Session) http.Handler {
+	mux := http.NewServeMux()
+	mux.HandleFunc("/api/status", incidents.handleStatus)
+	mux.HandleFunc("/healthz", handleHealth(s))
+	mux.HandleFunc("/live", handleLive)
+	mux.HandleFunc("/ready", handleReady(s))
+	mux.HandleFunc("/metrics", q.handleMetrics)
+	if APIToken != "" {
+		mux.Handle("/guilds/", requireToken(http.HandlerFunc(q.handleQueueAPI)))
+		mux.Handle("/dashboard", requireToken(http.HandlerFunc(q.handleDashboard)))
+		mux.Handle("/dashboard/kick", requireToken(sameOrigin(q.handleDashboardKick(s))))
+		mux.Handle("/dashboard/close", requireToken(sameOrigin(q.handleDashboardClose(s))))
+	}
+	return mux
+}
+
+// requireToken accepts the API token either as a bearer token or as the
+// basic auth password, so the dashboard can be opened in a browser.
+func requireToken(next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		token := strings.TrimPrefix(r.Header.Get("Authorization"), "Bearer ")
+		if _, password, ok := r.BasicAuth(); ok {
+			token = password
+		}
+		if subtle.ConstantTimeCompare([]byte(token), []byte(APIToken)) != 1 {
+			w.Header().Set("WWW-Authenticate", `Basic realm="standby"`)
+			http.Error(w, "unauthorized", http.StatusUnauthorized)
+			return
+		}
+		next.ServeHTTP(w, r)
+	})
+}
+
+// handleQueueAPI serves GET /guilds/{guild}/channels/{channel}/queue.
+func (q *queueState) handleQueueAPI(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodGet {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+	parts := strings.Split(strings.Trim(r.URL.Path, "/"), "/")
+	if len(parts) != 5 || parts[0] != "guilds" || parts[1] != GuildID ||
+		parts[2] != "channels" || parts[3] != ChannelID || parts[4] != "queue" {
+		http.NotFound(w, r)
+		return
+	}
+
+	q.Lock()
+	resp := q.apiQueueLocked()
+	q.Unlock()
+
+	writeJSON(w, http.StatusOK, resp)
+}
+
+// lock must be held
+func (q *queueState) apiQueueLocked() apiQueue {
+	resp := apiQueue{
+		Open:      q.currentMsgID != "",
+		MessageID: q.currentMsgID,
+		Queue:     []apiUser{},
+		Waitlist:  []apiUser{},
+	}
+	if resp.Open {
+		resp.Name = q.queueNameLocked()
+		resp.Size = q.preset.Size
+		openedAt := q.startTime
+		resp.OpenedAt = &openedAt
+		if q.details != (queueDetails{}) {
+			details := q.details
+			resp.Details = &details
+		}
+	}
+	for idx, user := range q.users {
+		u := q.apiUserLocked(user)
+		if idx < q.preset.Size {
+			resp.Queue = append(resp.Queue, u)
+		} else {
+			resp.Waitlist = append(resp.Waitlist, u)
+		}
+	}
+	return resp
+}
+
+// lock must be held
+func (q *queueState) apiUserLocked(user *discordgo.User) apiUser {
+	u := apiUser{
+		ID:       user.ID,
+		Username: user.Username,
+		Alias:    q.aliases[user.ID],
+	}
+	if joined, ok := q.joinedAt[user.ID]; ok {
+		u.JoinedAt = &joined
+	}
+	return u
+}
+
+func writeJSON(w http.ResponseWriter, status int, v any) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	if err := json.NewEncoder(w).Encode(v); err != nil {
+		slog.Error("error encoding response", "err", err)
+	}
+}
@@ -0,0 +1,187 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"maps"
+	"net/http"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// maxBackupSize bounds how much of a /standby-restore attachment is read.
+const maxBackupSize = 8 << 20
+
+// backupClient downloads /standby-restore attachments.
+var backupClient = &http.Client{Timeout: 30 * time.Second}
+
+// backup is the bot state that outlives a single queue.
+type backup struct {
+	CreatedAt time.Time             `json:"created_at"`
+	Aliases   map[string]string     `json:"aliases"`
+	IGNs      map[string]string     `json:"igns"`
+	GameRoles map[string][]string   `json:"game_roles"`
+	RiotLinks map[string]riotLink   `json:"riot_links"`
+	Ratings   map[string]float64    `json:"ratings"`
+	Results   []matchResult         `json:"results"`
+	Stats     map[string]*userStats `json:"stats"`
+	Templates map[string]string     `json:"templates"`
+	Presets   map[string]preset     `json:"presets"`
+	Games     map[string]game       `json:"games"`
+}
+
+func (q *queueState) handleBackupCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+
+	q.Lock()
+	b, err := json.MarshalIndent(backup{
+		CreatedAt: time.Now(),
+		Aliases:   q.aliases,
+		IGNs:      q.igns,
+		GameRoles: q.gameRoles,
+		RiotLinks: q.riotLinks,
+		Ratings:   q.ratings,
+		Results:   q.results,
+		Stats:     q.stats,
+		Templates: q.templates,
+		Presets:   q.presets,
+		Games:     q.knownGames,
+	}, "", "  ")
+	q.Unlock()
+	if err != nil {
+		interactionLogger(i).Error("error encoding backup", "err", err)
+		respondEphemeral(s, i, tr("Couldn't create a backup."))
+		return
+	}
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{
+			Files: []*discordgo.File{
+				{
+					Name:        fmt.Sprintf("standby-backup-%s.json", time.Now().Format("20060102-150405")),
+					ContentType: "application/json",
+					Reader:      bytes.NewReader(b),
+				},
+			},
+			Flags: discordgo.MessageFlagsEphemeral,
+		},
+	})
+}
+
+func (q *queueState) handleRestoreCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+	data := i.ApplicationCommandData()
+	attachment, ok := data.Resolved.Attachments[data.Options[0].Value.(string)]
+	if !ok {
+		respondEphemeral(s, i, tr("Attach a backup file made with /standby-backup."))
+		return
+	}
+
+	// Downloading can take longer than the interaction deadline.
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseDeferredChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{Flags: discordgo.MessageFlagsEphemeral},
+	})
+	logger := interactionLogger(i)
+
+	b, err := fetchBackup(attachment.URL)
+	if err != nil {
+		logger.Error("error reading backup", "err", err)
+		followupEphemeral(s, i, tr("Couldn't read that backup file."))
+		return
+	}
+
+	// Hand-edited presets and games could break the queue, so they must
+	// pass the checks /standby-preset and /standby-game make.
+	for name, p := range b.Presets {
+		reason := p.check()
+		if reason == "" && name != p.Name {
+			reason = tr("Preset **%s** is listed under **%s**.", p.Name, name)
+		}
+		if reason != "" {
+			followupEphemeral(s, i, tr("Couldn't restore that backup: %s", reason))
+			return
+		}
+	}
+	for key, g := range b.Games {
+		reason := g.check()
+		if reason == "" && key != gameKey(g.Name) {
+			reason = tr("Game **%s** is listed under **%s**.", g.Name, key)
+		}
+		if reason != "" {
+			followupEphemeral(s, i, tr("Couldn't restore that backup: %s", reason))
+			return
+		}
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if b.Aliases != nil {
+		q.aliases = b.Aliases
+	}
+	if b.IGNs != nil {
+		q.igns = b.IGNs
+	}
+	if b.GameRoles != nil {
+		q.gameRoles = b.GameRoles
+	}
+	if b.RiotLinks != nil {
+		q.riotLinks = b.RiotLinks
+	}
+	if b.Ratings != nil {
+		q.ratings = b.Ratings
+	}
+	if b.Stats != nil {
+		q.stats = b.Stats
+	}
+	if b.Templates != nil {
+		q.templates = b.Templates
+	}
+	if b.Presets != nil {
+		q.presets = b.Presets
+	}
+	if b.Games != nil {
+		q.knownGames = b.Games
+	}
+	q.results = b.Results
+	if q.currentMsgID != "" {
+		if err := q.editQueueMessageLocked(s); err != nil {
+			logger.Error("error editing message after restore", "err", err)
+		}
+	}
+
+	followupEphemeral(s, i, tr("Restored the backup from <t:%d:f>.", b.CreatedAt.Unix()))
+}
+
+func fetchBackup(url string) (*backup, error) {
+	resp, err := backupClient.Get(url)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("unexpected status %s", resp.Status)
+	}
+
+	var b backup
+	if err := json.NewDecoder(io.LimitReader(resp.Body, maxBackupSize)).Decode(&b); err != nil {
+		return nil, err
+	}
+	// A hand-edited file may hold null counters, which statsLocked would
+	// hand out as nil.
+	maps.DeleteFunc(b.Stats, func(_ string, st *userStats) bool {
+		return st == nil
+	})
+	return &b, nil
+}
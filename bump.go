@@ -0,0 +1,60 @@
+package main
+
+import (
+	"log/slog"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+var (
+	// BumpAfterMessages reposts the queue message once this many chat
+	// messages were sent below it. 0 disables it.
+	BumpAfterMessages = intEnv("STANDBY_BUMP_AFTER_MESSAGES", 0)
+	// BumpAfter reposts the queue message once chat has been going on below
+	// it for this long. 0 disables it.
+	BumpAfter = durationEnv("STANDBY_BUMP_AFTER", 0)
+)
+
+// handleMessageCreate counts chat below the queue message and bumps it back
+// to the bottom of the channel once it has been buried.
+func (q *queueState) handleMessageCreate(s *discordgo.Session, m *discordgo.MessageCreate) {
+	if BumpAfterMessages <= 0 && BumpAfter <= 0 {
+		return
+	}
+	if m.Author == nil || m.Author.ID == s.State.User.ID {
+		return
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if q.currentMsgID == "" || m.ChannelID != q.channelLocked() {
+		return
+	}
+	q.chatCount++
+	if q.chatStart.IsZero() {
+		q.chatStart = time.Now()
+	}
+	if (BumpAfterMessages > 0 && q.chatCount >= BumpAfterMessages) ||
+		(BumpAfter > 0 && time.Since(q.chatStart) >= BumpAfter) {
+		q.bumpLocked(s)
+	}
+}
+
+// bumpLocked reposts the queue message at the bottom of the channel and
+// deletes the old one.
+//
+// lock must be held
+func (q *queueState) bumpLocked(s *discordgo.Session) {
+	old := q.currentMsgID
+	if err := q.sendQueueMessageLocked(s); err != nil {
+		slog.Error("error bumping queue message", "err", err)
+		return
+	}
+	q.chatCount = 0
+	q.chatStart = time.Time{}
+	if err := deleteMessage(s, q.channelLocked(), old); err != nil {
+		slog.Error("error deleting bumped queue message", "err", err, "message", old)
+	}
+}
@@ -0,0 +1,158 @@
+package main
+
+import (
+	"fmt"
+	"log/slog"
+	"os"
+	"slices"
+	"strconv"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// roleRequirement asks for at least Count players who can play Role.
+type roleRequirement struct {
+	Role  string
+	Count int
+}
+
+// Composition is the GameRoles a full stack must cover before it is ready,
+// such as "Support:1,Tank:1". A player only counts towards one requirement.
+var Composition = parseComposition(os.Getenv("STANDBY_COMPOSITION"), GameRoles)
+
+// parseComposition parses a composition, panicking on roles players can't
+// pick from roles, since the stack could never become ready.
+func parseComposition(v string, roles []string) []roleRequirement {
+	items := splitList(v)
+	if len(items) > 0 && len(roles) == 0 {
+		panic("STANDBY_COMPOSITION needs STANDBY_GAME_ROLES for players to pick from")
+	}
+	pickable := roles[:min(len(roles), maxGameRoles)]
+	var reqs []roleRequirement
+	for _, item := range items {
+		role, count, ok := strings.Cut(item, ":")
+		n := 1
+		if ok {
+			var err error
+			if n, err = strconv.Atoi(strings.TrimSpace(count)); err != nil || n < 1 {
+				panic(fmt.Sprintf("invalid STANDBY_COMPOSITION entry %q", item))
+			}
+		}
+		role = strings.TrimSpace(role)
+		if !slices.Contains(pickable, role) {
+			panic(fmt.Sprintf("invalid STANDBY_COMPOSITION entry %q: %q isn't one of STANDBY_GAME_ROLES", item, role))
+		}
+		reqs = append(reqs, roleRequirement{Role: role, Count: n})
+	}
+	return reqs
+}
+
+// missingRolesLocked returns the requirements the stack can't cover with
+// the roles its players picked, or nil if the composition is valid.
+//
+// lock must be held
+func (q *queueState) missingRolesLocked(stack []*discordgo.User) []roleRequirement {
+	if len(Composition) == 0 {
+		return nil
+	}
+
+	// Match players to requirement slots with augmenting paths, so a
+	// player who can play several roles fills whichever one is needed.
+	var slots []string
+	for _, req := range Composition {
+		for n := 0; n < req.Count; n++ {
+			slots = append(slots, req.Role)
+		}
+	}
+	filledBy := make([]int, len(slots))
+	for idx := range filledBy {
+		filledBy[idx] = -1
+	}
+	var assign func(player int, seen []bool) bool
+	assign = func(player int, seen []bool) bool {
+		for _, role := range q.gameRoles[stack[player].ID] {
+			for slot, want := range slots {
+				if want != role || seen[slot] {
+					continue
+				}
+				seen[slot] = true
+				if filledBy[slot] < 0 || assign(filledBy[slot], seen) {
+					filledBy[slot] = player
+					return true
+				}
+			}
+		}
+		return false
+	}
+	for player := range stack {
+		assign(player, make([]bool, len(slots)))
+	}
+
+	var missing []roleRequirement
+	for slot, role := range slots {
+		if filledBy[slot] >= 0 {
+			continue
+		}
+		if n := len(missing); n > 0 && missing[n-1].Role == role {
+			missing[n-1].Count++
+		} else {
+			missing = append(missing, roleRequirement{Role: role, Count: 1})
+		}
+	}
+	return missing
+}
+
+// updateCompositionLocked tells the channel which roles a full stack still
+// needs, pinging the players in it who haven't picked roles yet. The message
+// is replaced when either changes and removed once nothing is missing.
+//
+// lock must be held
+func (q *queueState) updateCompositionLocked(s *discordgo.Session, missing []roleRequirement) {
+	needed := make([]string, len(missing))
+	for idx, req := range missing {
+		needed[idx] = req.Role
+		if req.Count > 1 {
+			needed[idx] = fmt.Sprintf("%d× %s", req.Count, req.Role)
+		}
+	}
+	var mentions []string
+	if len(missing) > 0 {
+		for _, user := range q.unpickedLocked(q.users[:q.preset.Size]) {
+			mentions = append(mentions, mention(user))
+		}
+	}
+	key := strings.Join(needed, ", ")
+	if key+strings.Join(mentions, "") == q.compositionMissing {
+		return
+	}
+	q.clearCompositionLocked(s)
+	if key == "" {
+		return
+	}
+
+	content := tr("The stack is full but needs %s. An admin can /standby-promote a waitlisted player who plays it.", key)
+	if len(mentions) > 0 {
+		content = tr("The stack is full but needs %s. %s, pick your roles with the Roles button on the queue, or an admin can /standby-promote a waitlisted player.", key, strings.Join(mentions, " "))
+	}
+	m, err := sendMessage(s, q.channelLocked(), &discordgo.MessageSend{Content: content})
+	if err != nil {
+		slog.Error("error sending composition message", "err", err)
+		return
+	}
+	q.compositionMsgID = m.ID
+	q.compositionMissing = key + strings.Join(mentions, "")
+}
+
+// lock must be held
+func (q *queueState) clearCompositionLocked(s *discordgo.Session) {
+	q.compositionMissing = ""
+	if q.compositionMsgID == "" {
+		return
+	}
+	msgID := q.compositionMsgID
+	q.compositionMsgID = ""
+	if err := deleteMessage(s, q.channelLocked(), msgID); err != nil {
+		slog.Error("error deleting composition message", "err", err, "message", msgID)
+	}
+}
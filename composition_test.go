@@ -0,0 +1,93 @@
+package main
+
+import (
+	"fmt"
+	"strings"
+	"testing"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+func TestCompositionStackAddedByCommand(t *testing.T) {
+	oldChannel, oldTimeout, oldRoles, oldComposition := ChannelID, PromotionTimeout, GameRoles, Composition
+	t.Cleanup(func() {
+		ChannelID, PromotionTimeout, GameRoles, Composition = oldChannel, oldTimeout, oldRoles, oldComposition
+	})
+	ChannelID = "chan"
+	PromotionTimeout = 0
+	GameRoles = []string{"Tank", "DPS", "Support"}
+	Composition = parseComposition("Support", GameRoles)
+
+	s, _ := newFakeSession(t)
+	q := newQueueState()
+	q.currentMsgID = "queue"
+	q.creatorID = "mod"
+
+	users := make([]*discordgo.User, 5)
+	for idx := range users {
+		users[idx] = &discordgo.User{ID: fmt.Sprintf("u%d", idx+1), Username: fmt.Sprintf("user%d", idx+1)}
+	}
+	q.Lock()
+	for _, user := range users[:4] {
+		q.gameRoles[user.ID] = []string{"DPS"}
+		q.addUserLocked(s, user)
+	}
+	q.Unlock()
+
+	// A moderator adds the last player, who never saw the picker.
+	q.handleAddToQueueCommand(s, &discordgo.InteractionCreate{Interaction: &discordgo.Interaction{
+		ID:     "add",
+		Type:   discordgo.InteractionApplicationCommand,
+		Member: &discordgo.Member{User: &discordgo.User{ID: "mod"}},
+		Data: discordgo.ApplicationCommandInteractionData{
+			Name:     addToQueueCommand,
+			TargetID: users[4].ID,
+			Resolved: &discordgo.ApplicationCommandInteractionDataResolved{
+				Users: map[string]*discordgo.User{users[4].ID: users[4]},
+			},
+		},
+	}})
+
+	q.Lock()
+	defer q.Unlock()
+	if q.notifyMsgID != "" {
+		t.Fatal("ready ping sent without a Support player")
+	}
+	if q.compositionMsgID == "" || !strings.Contains(q.compositionMissing, mention(users[4])) {
+		t.Errorf("composition message %q doesn't ping the added player (lists %q)", q.compositionMsgID, q.compositionMissing)
+	}
+	var hasPicker bool
+	for _, row := range q.queueComponentsLocked() {
+		for _, c := range row.(discordgo.ActionsRow).Components {
+			if b, ok := c.(discordgo.Button); ok && b.CustomID == "open_role_picker" {
+				hasPicker = true
+			}
+		}
+	}
+	if !hasPicker {
+		t.Fatal("queue message has no Roles button")
+	}
+
+	// They open the picker from the queue message and pick Support.
+	q.handlePickGameRoleLocked(s, &discordgo.InteractionCreate{Interaction: &discordgo.Interaction{
+		ID:     "pick",
+		Type:   discordgo.InteractionMessageComponent,
+		Member: &discordgo.Member{User: users[4]},
+		Data: discordgo.MessageComponentInteractionData{
+			CustomID: "pick_game_role",
+			Values:   []string{"Support"},
+		},
+	}})
+	// Refresh now rather than after the debounce.
+	q.refreshTimer.Stop()
+	q.refreshTimer = nil
+	if err := q.refreshLocked(s); err != nil {
+		t.Fatalf("refreshLocked() = %v", err)
+	}
+	if q.notifyMsgID == "" {
+		t.Error("ready ping not sent after the added player picked Support")
+	}
+	if q.compositionMsgID != "" {
+		t.Errorf("composition message %q left up", q.compositionMsgID)
+	}
+}
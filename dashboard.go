@@ -0,0 +1,101 @@
+package main
+
+import (
+	"html/template"
+	"log/slog"
+	"net/http"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+var dashboardTmpl = template.Must(template.New("dashboard").Parse(`<!DOCTYPE html>
+<html>
+<head><title>{{if .Open}}{{.Name}}{{else}}Standby Queue{{end}}</title></head>
+<body>
+<h1>{{if .Open}}{{.Name}}{{else}}Standby Queue{{end}}</h1>
+{{if .Open}}
+<p>Opened {{.OpenedAt.Format "2006-01-02 15:04 MST"}}</p>
+<h2>Queue ({{len .Queue}}/{{.Size}})</h2>
+<ul>{{range .Queue}}{{template "user" .}}{{end}}</ul>
+{{if .Waitlist}}
+<h2>Waitlist ({{len .Waitlist}})</h2>
+<ul>{{range .Waitlist}}{{template "user" .}}{{end}}</ul>
+{{end}}
+<form method="post" action="/dashboard/close"><button>Close queue</button></form>
+{{else}}
+<p>Queue is closed.</p>
+{{end}}
+</body>
+</html>
+{{define "user"}}<li>
+{{if .Alias}}{{.Alias}} ({{.Username}}){{else}}{{.Username}}{{end}}
+{{with .JoinedAt}}joined {{.Format "15:04 MST"}}{{end}}
+<form method="post" action="/dashboard/kick" style="display:inline">
+<input type="hidden" name="user_id" value="{{.ID}}"><button>Kick</button>
+</form>
+</li>{{end}}`))
+
+// sameOrigin rejects cross-site form posts, since browsers resend basic auth
+// credentials automatically.
+func sameOrigin(next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if origin := r.Header.Get("Origin"); origin != "" && origin != "https://"+r.Host && origin != "http://"+r.Host {
+			http.Error(w, "forbidden", http.StatusForbidden)
+			return
+		}
+		next.ServeHTTP(w, r)
+	})
+}
+
+func (q *queueState) handleDashboard(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodGet {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+
+	q.Lock()
+	data := q.apiQueueLocked()
+	q.Unlock()
+
+	w.Header().Set("Content-Type", "text/html; charset=utf-8")
+	if err := dashboardTmpl.Execute(w, data); err != nil {
+		slog.Error("error rendering dashboard", "err", err)
+	}
+}
+
+func (q *queueState) handleDashboardKick(s *discordgo.Session) http.HandlerFunc {
+	return func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		q.Lock()
+		defer q.Unlock()
+
+		if user := q.removeUserLocked(s, r.FormValue("user_id")); user != nil {
+			q.recordActionLocked(user, actionKick)
+			if err := q.refreshLocked(s); err != nil {
+				slog.Error("error editing message kicking from dashboard", "err", err, "user", user.ID)
+			}
+		}
+		http.Redirect(w, r, "/dashboard", http.StatusSeeOther)
+	}
+}
+
+func (q *queueState) handleDashboardClose(s *discordgo.Session) http.HandlerFunc {
+	return func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		q.Lock()
+		defer q.Unlock()
+
+		if q.currentMsgID != "" {
+			q.closeQueueLocked(s)
+		}
+		http.Redirect(w, r, "/dashboard", http.StatusSeeOther)
+	}
+}
@@ -0,0 +1,112 @@
+package main
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// detailsModalID is the custom ID of the /standby details modal. A ":thread"
+// suffix carries the command's thread option through the modal.
+const detailsModalID = "standby_details"
+
+// queueDetails is what the queue creator can fill in through the /standby
+// details modal.
+type queueDetails struct {
+	Title string `json:"title,omitempty"`
+	Game  string `json:"game,omitempty"`
+	Notes string `json:"notes,omitempty"`
+}
+
+// detailsModal asks for the details of a queue about to be opened.
+func detailsModal(thread bool) *discordgo.InteractionResponse {
+	customID := detailsModalID
+	if thread {
+		customID += ":thread"
+	}
+	return &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseModal,
+		Data: &discordgo.InteractionResponseData{
+			CustomID: customID,
+			Title:    tr("Open standby queue"),
+			Components: []discordgo.MessageComponent{
+				discordgo.ActionsRow{Components: []discordgo.MessageComponent{
+					discordgo.TextInput{
+						CustomID:  "title",
+						Label:     tr("Title"),
+						Style:     discordgo.TextInputShort,
+						MaxLength: 100,
+					},
+				}},
+				discordgo.ActionsRow{Components: []discordgo.MessageComponent{
+					discordgo.TextInput{
+						CustomID:  "game",
+						Label:     tr("Game"),
+						Style:     discordgo.TextInputShort,
+						MaxLength: 100,
+					},
+				}},
+				discordgo.ActionsRow{Components: []discordgo.MessageComponent{
+					discordgo.TextInput{
+						CustomID:    "notes",
+						Label:       tr("Notes"),
+						Style:       discordgo.TextInputParagraph,
+						Placeholder: tr("e.g. ranked only, mic required"),
+						MaxLength:   500,
+					},
+				}},
+			},
+		},
+	}
+}
+
+// handleDetailsSubmit opens the queue with the details from the modal.
+func (q *queueState) handleDetailsSubmit(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	data := i.ModalSubmitData()
+	var details queueDetails
+	for _, row := range data.Components {
+		for _, c := range row.(*discordgo.ActionsRow).Components {
+			input := c.(*discordgo.TextInput)
+			value := strings.TrimSpace(input.Value)
+			switch input.CustomID {
+			case "title":
+				details.Title = value
+			case "game":
+				details.Game = value
+			case "notes":
+				details.Notes = value
+			}
+		}
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if q.currentMsgID != "" {
+		respondEphemeral(s, i, tr("There is already an existing queue."))
+		return
+	}
+	q.details = details
+	q.startQueueLocked(s, i, strings.HasSuffix(data.CustomID, ":thread"))
+}
+
+// detailsTextLocked renders the queue details for the top of the embed, or
+// "" if there are none.
+//
+// lock must be held
+func (q *queueState) detailsTextLocked() string {
+	var sb strings.Builder
+	if q.details.Title != "" {
+		sb.WriteString(fmt.Sprintf("## %s\n", q.details.Title))
+	}
+	if q.details.Game != "" {
+		sb.WriteString(tr("**Game:** %s\n", q.details.Game))
+	}
+	if q.details.Notes != "" {
+		for _, line := range strings.Split(q.details.Notes, "\n") {
+			sb.WriteString("> " + line + "\n")
+		}
+	}
+	return sb.String()
+}
@@ -0,0 +1,141 @@
+package main
+
+import (
+	"math/rand"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// draftOrder is the snake order captains pick in: A, B, B, A, ...
+var draftOrder = [4]int{0, 1, 1, 0}
+
+// draft is a captains draft of the first two teams' worth of queued users.
+type draft struct {
+	captains [2]*discordgo.User
+	teams    [2][]*discordgo.User
+	pool     []*discordgo.User
+	picks    int
+
+	// msgID is the draft message, so that selects on older draft messages
+	// are rejected.
+	msgID string
+}
+
+func (d *draft) turn() int {
+	return draftOrder[d.picks%len(draftOrder)]
+}
+
+// lock must be held
+func (q *queueState) startDraftLocked(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if len(q.users) < 2*q.preset.TeamSize {
+		respondEphemeral(s, i, tr("Need %d players to draft teams.", 2*q.preset.TeamSize))
+		return
+	}
+
+	pool := append([]*discordgo.User(nil), q.users[:2*q.preset.TeamSize]...)
+	rand.Shuffle(len(pool), func(x, y int) {
+		pool[x], pool[y] = pool[y], pool[x]
+	})
+	d := &draft{
+		captains: [2]*discordgo.User{pool[0], pool[1]},
+		pool:     pool[2:],
+	}
+	d.teams[0] = []*discordgo.User{pool[0]}
+	d.teams[1] = []*discordgo.User{pool[1]}
+	q.draft = d
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: q.draftMessageLocked(),
+	})
+	m, err := s.InteractionResponse(i.Interaction)
+	if err != nil {
+		// Without the message ID no pick can be matched to this draft.
+		interactionLogger(i).Error("error fetching draft message", "err", err)
+		q.draft = nil
+		return
+	}
+	d.msgID = m.ID
+}
+
+// lock must be held
+func (q *queueState) draftPickLocked(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	d := q.draft
+	if d == nil || d.msgID != i.Message.ID {
+		respondEphemeral(s, i, tr("This draft is no longer active."))
+		return
+	}
+	captain := d.captains[d.turn()]
+	if i.Member.User.ID != captain.ID {
+		respondEphemeral(s, i, tr("It's %s's turn to pick.", q.nameLocked(captain)))
+		return
+	}
+
+	pickedID := i.MessageComponentData().Values[0]
+	for idx, user := range d.pool {
+		if user.ID == pickedID {
+			d.teams[d.turn()] = append(d.teams[d.turn()], user)
+			d.pool = append(d.pool[:idx], d.pool[idx+1:]...)
+			d.picks++
+			break
+		}
+	}
+
+	data := q.draftMessageLocked()
+	if len(d.pool) == 0 {
+		q.draft = nil
+	}
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseUpdateMessage,
+		Data: data,
+	})
+}
+
+// lock must be held
+func (q *queueState) draftMessageLocked() *discordgo.InteractionResponseData {
+	d := q.draft
+	embed := &discordgo.MessageEmbed{
+		Type:  discordgo.EmbedTypeRich,
+		Title: tr("Captains Draft"),
+		Color: 0x0099FF,
+		Fields: []*discordgo.MessageEmbedField{
+			q.teamFieldLocked(tr("Team %s", q.nameLocked(d.captains[0])), d.teams[0]),
+			q.teamFieldLocked(tr("Team %s", q.nameLocked(d.captains[1])), d.teams[1]),
+		},
+	}
+	if len(d.pool) == 0 {
+		embed.Title = tr("Teams")
+		return &discordgo.InteractionResponseData{
+			Embeds:     []*discordgo.MessageEmbed{embed},
+			Components: []discordgo.MessageComponent{},
+		}
+	}
+
+	remaining := make([]string, len(d.pool))
+	options := make([]discordgo.SelectMenuOption, len(d.pool))
+	for idx, user := range d.pool {
+		remaining[idx] = q.mentionLocked(user)
+		options[idx] = discordgo.SelectMenuOption{
+			Label: q.nameLocked(user),
+			Value: user.ID,
+		}
+	}
+	embed.Description = tr("Remaining: %s", strings.Join(remaining, ", "))
+
+	return &discordgo.InteractionResponseData{
+		Embeds: []*discordgo.MessageEmbed{embed},
+		Components: []discordgo.MessageComponent{
+			discordgo.ActionsRow{
+				Components: []discordgo.MessageComponent{
+					discordgo.SelectMenu{
+						MenuType:    discordgo.StringSelectMenu,
+						CustomID:    "draft_pick",
+						Placeholder: tr("%s picks", q.nameLocked(d.captains[d.turn()])),
+						Options:     options,
+					},
+				},
+			},
+		},
+	}
+}
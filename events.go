@@ -0,0 +1,182 @@
+package main
+
+import (
+	"fmt"
+	"log/slog"
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+const defaultEventLead = 15 * time.Minute
+
+// eventLink ties a Discord scheduled event to the queue, which opens lead
+// before the event starts and closes when it ends.
+type eventLink struct {
+	lead  time.Duration
+	timer *time.Timer
+	fired bool
+}
+
+func (q *queueState) handleEventCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+
+	var eventID string
+	lead := defaultEventLead
+	for _, opt := range i.ApplicationCommandData().Options {
+		switch opt.Name {
+		case "event":
+			eventID = opt.StringValue()
+		case "lead":
+			lead = time.Duration(opt.IntValue()) * time.Minute
+		}
+	}
+
+	ev, err := s.GuildScheduledEvent(GuildID, eventID, false)
+	if err != nil {
+		respondEphemeral(s, i, tr("Couldn't find that event."))
+		return
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	openAt := q.scheduleEventLocked(s, ev, lead)
+	respondEphemeral(s, i, tr("Queue will open <t:%d:R> for **%s**.", openAt.Unix(), ev.Name))
+}
+
+func handleEventAutocomplete(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	events, err := s.GuildScheduledEvents(GuildID, false)
+	if err != nil {
+		slog.Error("error fetching scheduled events", "err", err)
+	}
+
+	choices := []*discordgo.ApplicationCommandOptionChoice{}
+	for _, ev := range events {
+		if ev.Status != discordgo.GuildScheduledEventStatusScheduled {
+			continue
+		}
+		choices = append(choices, &discordgo.ApplicationCommandOptionChoice{
+			Name:  fmt.Sprintf("%.70s (%s)", ev.Name, ev.ScheduledStartTime.Format("Jan 2 15:04 MST")),
+			Value: ev.ID,
+		})
+		if len(choices) == 25 {
+			break
+		}
+	}
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionApplicationCommandAutocompleteResult,
+		Data: &discordgo.InteractionResponseData{
+			Choices: choices,
+		},
+	})
+}
+
+// scheduleEventLocked (re)arms the timer that opens the queue for an event
+// and returns when it will fire.
+//
+// lock must be held
+func (q *queueState) scheduleEventLocked(s *discordgo.Session, ev *discordgo.GuildScheduledEvent, lead time.Duration) time.Time {
+	if link, ok := q.eventLinks[ev.ID]; ok {
+		link.timer.Stop()
+	}
+
+	eventID := ev.ID
+	openAt := ev.ScheduledStartTime.Add(-lead)
+	link := &eventLink{lead: lead}
+	link.timer = time.AfterFunc(time.Until(openAt), func() {
+		q.openForEvent(s, eventID)
+	})
+	q.eventLinks[eventID] = link
+	return openAt
+}
+
+// openForEvent opens the queue, if it isn't already, and pings the users
+// interested in the event.
+func (q *queueState) openForEvent(s *discordgo.Session, eventID string) {
+	ev, err := s.GuildScheduledEvent(GuildID, eventID, false)
+	if err != nil {
+		slog.Error("error fetching scheduled event", "err", err, "event", eventID)
+		return
+	}
+	interested, err := s.GuildScheduledEventUsers(GuildID, eventID, 100, false, "", "")
+	if err != nil {
+		slog.Error("error fetching scheduled event users", "err", err, "event", eventID)
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	link, ok := q.eventLinks[eventID]
+	if !ok {
+		return
+	}
+	link.fired = true
+
+	if q.currentMsgID == "" {
+		q.preset = q.presetLocked(defaultPreset)
+		q.game = ""
+		if err := q.openQueueLocked(s); err != nil {
+			slog.Error("error opening queue for event", "err", err, "event", eventID)
+			return
+		}
+		q.eventID = eventID
+	}
+
+	mentions := make([]string, 0, len(interested))
+	for _, u := range interested {
+		mentions = append(mentions, fmt.Sprintf("<@%s>", u.User.ID))
+	}
+	content := tr("Queue is open for **%s** starting <t:%d:R>! %s", ev.Name, ev.ScheduledStartTime.Unix(), strings.Join(mentions, " "))
+	if _, err := sendMessage(s, ChannelID, &discordgo.MessageSend{Content: content}); err != nil {
+		slog.Error("error sending event notification", "err", err, "event", eventID)
+	}
+}
+
+func (q *queueState) handleEventUpdate(s *discordgo.Session, e *discordgo.GuildScheduledEventUpdate) {
+	q.Lock()
+	defer q.Unlock()
+
+	link, ok := q.eventLinks[e.ID]
+	if !ok {
+		return
+	}
+	switch e.Status {
+	case discordgo.GuildScheduledEventStatusCompleted, discordgo.GuildScheduledEventStatusCanceled:
+		q.unlinkEventLocked(s, e.ID)
+	case discordgo.GuildScheduledEventStatusScheduled:
+		// The start time may have moved.
+		if !link.fired {
+			q.scheduleEventLocked(s, e.GuildScheduledEvent, link.lead)
+		}
+	}
+}
+
+func (q *queueState) handleEventDelete(s *discordgo.Session, e *discordgo.GuildScheduledEventDelete) {
+	q.Lock()
+	defer q.Unlock()
+
+	q.unlinkEventLocked(s, e.ID)
+}
+
+// unlinkEventLocked forgets an event and closes the queue if the event
+// opened it.
+//
+// lock must be held
+func (q *queueState) unlinkEventLocked(s *discordgo.Session, eventID string) {
+	link, ok := q.eventLinks[eventID]
+	if !ok {
+		return
+	}
+	link.timer.Stop()
+	delete(q.eventLinks, eventID)
+
+	if q.eventID == eventID && q.currentMsgID != "" {
+		q.closeQueueLocked(s)
+	}
+}
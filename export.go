@@ -0,0 +1,97 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"maps"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+type exportSettings struct {
+	Preset          string            `json:"preset"`
+	StackSize       int               `json:"stack_size"`
+	PromotionPolicy promotionPolicy   `json:"promotion_policy,omitempty"`
+	TeamSplit       string            `json:"team_split,omitempty"`
+	MapPool         []string          `json:"map_pool,omitempty"`
+	Locale          string            `json:"locale,omitempty"`
+	Timezone        string            `json:"timezone"`
+	Templates       map[string]string `json:"templates,omitempty"`
+}
+
+type exportEvent struct {
+	At     time.Time `json:"at"`
+	Action string    `json:"action"`
+	User   *apiUser  `json:"user,omitempty"`
+}
+
+type exportData struct {
+	ExportedAt time.Time      `json:"exported_at"`
+	Queue      apiQueue       `json:"queue"`
+	Settings   exportSettings `json:"settings"`
+	History    []exportEvent  `json:"history"`
+}
+
+// exportLocked snapshots the queue for export. The result must not share
+// state with q, since it is encoded after the lock is released.
+//
+// lock must be held
+func (q *queueState) exportLocked() exportData {
+	data := exportData{
+		ExportedAt: time.Now(),
+		Queue:      q.apiQueueLocked(),
+		Settings: exportSettings{
+			Preset:          q.preset.Name,
+			StackSize:       q.preset.Size,
+			PromotionPolicy: PromotionPolicy,
+			TeamSplit:       q.preset.TeamSplit,
+			MapPool:         MapPool,
+			Locale:          Locale,
+			Timezone:        Timezone.String(),
+			Templates:       maps.Clone(q.templates),
+		},
+		History: make([]exportEvent, 0, len(q.history)),
+	}
+	for _, action := range q.history {
+		ev := exportEvent{At: action.at, Action: string(action.verb)}
+		if action.user != nil {
+			u := q.apiUserLocked(action.user)
+			ev.User = &u
+		}
+		data.History = append(data.History, ev)
+	}
+	return data
+}
+
+func (q *queueState) handleExportCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+
+	q.Lock()
+	data := q.exportLocked()
+	q.Unlock()
+
+	b, err := json.MarshalIndent(data, "", "  ")
+	if err != nil {
+		interactionLogger(i).Error("error encoding export", "err", err)
+		respondEphemeral(s, i, tr("Couldn't export the queue."))
+		return
+	}
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{
+			Files: []*discordgo.File{
+				{
+					Name:        fmt.Sprintf("standby-%s.json", data.ExportedAt.Format("20060102-150405")),
+					ContentType: "application/json",
+					Reader:      bytes.NewReader(b),
+				},
+			},
+			Flags: discordgo.MessageFlagsEphemeral,
+		},
+	})
+}
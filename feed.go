@@ -0,0 +1,94 @@
+package main
+
+import (
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+type actionVerb string
+
+const (
+	actionJoin    actionVerb = "join"
+	actionLeave   actionVerb = "leave"
+	actionKick    actionVerb = "kick"
+	actionPromote actionVerb = "promote"
+	actionShuffle actionVerb = "shuffle"
+	// actionFill and actionClose only appear in the history, and have no
+	// user.
+	actionFill  actionVerb = "fill"
+	actionClose actionVerb = "close"
+)
+
+// feedSize is how many recent actions the queue embed shows.
+const feedSize = 3
+
+// feedToggleWindow is how close together a user's joins and leaves must be
+// to be collapsed into a single feed entry.
+const feedToggleWindow = time.Minute
+
+type queueAction struct {
+	user *discordgo.User
+	verb actionVerb
+	at   time.Time
+}
+
+// recordActionLocked adds an action to the queue's feed, keeping the last
+// feedSize. A join or leave right after the same user's previous join or
+// leave replaces it, so toggling doesn't flood the feed.
+//
+// lock must be held
+func (q *queueState) recordActionLocked(user *discordgo.User, verb actionVerb) {
+	now := time.Now()
+	q.recordHistoryLocked(queueAction{user: user, verb: verb, at: now})
+	if verb == actionKick {
+		q.recordKickLocked(user.ID)
+	}
+	if n := len(q.feed); n > 0 {
+		last := q.feed[n-1]
+		if last.user.ID == user.ID && isToggle(last.verb) && isToggle(verb) && now.Sub(last.at) < feedToggleWindow {
+			q.feed = q.feed[:n-1]
+		}
+	}
+	q.feed = append(q.feed, queueAction{user: user, verb: verb, at: now})
+	if len(q.feed) > feedSize {
+		q.feed = q.feed[len(q.feed)-feedSize:]
+	}
+}
+
+func isToggle(verb actionVerb) bool {
+	return verb == actionJoin || verb == actionLeave
+}
+
+// feedTextLocked renders the feed, oldest first.
+//
+// lock must be held
+func (q *queueState) feedTextLocked() string {
+	var sb strings.Builder
+	for _, action := range q.feed {
+		sb.WriteString(q.actionTextLocked(action))
+	}
+	return sb.String()
+}
+
+// lock must be held
+func (q *queueState) actionTextLocked(action queueAction) string {
+	switch action.verb {
+	case actionJoin:
+		return tr("➕ %s joined\n", q.nameLocked(action.user))
+	case actionLeave:
+		return tr("➖ %s left\n", q.nameLocked(action.user))
+	case actionKick:
+		return tr("👢 %s was kicked\n", q.nameLocked(action.user))
+	case actionPromote:
+		return tr("⬆️ %s was promoted\n", q.nameLocked(action.user))
+	case actionShuffle:
+		return tr("🔀 %s shuffled the queue\n", q.nameLocked(action.user))
+	case actionFill:
+		return tr("✅ The stack filled\n")
+	case actionClose:
+		return tr("🔒 The queue closed\n")
+	}
+	return ""
+}
@@ -0,0 +1,133 @@
+package main
+
+import (
+	"fmt"
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+var (
+	alice = &discordgo.User{ID: "1", Username: "alice"}
+	bob   = &discordgo.User{ID: "2", Username: "bob"}
+	carol = &discordgo.User{ID: "3", Username: "carol"}
+	dave  = &discordgo.User{ID: "4", Username: "dave"}
+)
+
+func TestRecordActionLocked(t *testing.T) {
+	longAgo := time.Now().Add(-2 * feedToggleWindow)
+
+	tests := []struct {
+		name string
+		// feed is what the feed holds before recording.
+		feed   []queueAction
+		record []queueAction
+		want   []string
+	}{
+		{
+			name: "keeps the last feedSize",
+			record: []queueAction{
+				{user: alice, verb: actionJoin},
+				{user: bob, verb: actionJoin},
+				{user: carol, verb: actionJoin},
+				{user: dave, verb: actionJoin},
+			},
+			want: []string{"bob join", "carol join", "dave join"},
+		},
+		{
+			name: "collapses a toggle within the window",
+			record: []queueAction{
+				{user: alice, verb: actionJoin},
+				{user: alice, verb: actionLeave},
+			},
+			want: []string{"alice leave"},
+		},
+		{
+			name: "collapses repeated toggles",
+			record: []queueAction{
+				{user: bob, verb: actionJoin},
+				{user: alice, verb: actionJoin},
+				{user: alice, verb: actionLeave},
+				{user: alice, verb: actionJoin},
+			},
+			want: []string{"bob join", "alice join"},
+		},
+		{
+			name: "keeps a toggle outside the window",
+			feed: []queueAction{
+				{user: alice, verb: actionJoin, at: longAgo},
+			},
+			record: []queueAction{
+				{user: alice, verb: actionLeave},
+			},
+			want: []string{"alice join", "alice leave"},
+		},
+		{
+			name: "keeps toggles by different users",
+			record: []queueAction{
+				{user: alice, verb: actionJoin},
+				{user: bob, verb: actionLeave},
+			},
+			want: []string{"alice join", "bob leave"},
+		},
+		{
+			name: "keeps actions that aren't toggles",
+			record: []queueAction{
+				{user: alice, verb: actionJoin},
+				{user: alice, verb: actionKick},
+			},
+			want: []string{"alice join", "alice kick"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			q := &queueState{feed: slices.Clone(tt.feed), stats: map[string]*userStats{}}
+			for _, action := range tt.record {
+				q.recordActionLocked(action.user, action.verb)
+			}
+
+			var got []string
+			for _, action := range q.feed {
+				got = append(got, fmt.Sprintf("%s %s", action.user.Username, action.verb))
+			}
+			if !slices.Equal(got, tt.want) {
+				t.Errorf("feed = %q, want %q", got, tt.want)
+			}
+			if len(q.history) != len(tt.record) {
+				t.Errorf("history has %d actions, want %d", len(q.history), len(tt.record))
+			}
+		})
+	}
+}
+
+func TestActionTextLocked(t *testing.T) {
+	tests := []struct {
+		action queueAction
+		want   string
+	}{
+		{queueAction{user: alice, verb: actionJoin}, "➕ alice joined\n"},
+		{queueAction{user: alice, verb: actionLeave}, "➖ alice left\n"},
+		{queueAction{user: alice, verb: actionKick}, "👢 alice was kicked\n"},
+		{queueAction{user: alice, verb: actionPromote}, "⬆️ alice was promoted\n"},
+		{queueAction{user: alice, verb: actionShuffle}, "🔀 alice shuffled the queue\n"},
+		{queueAction{user: bob, verb: actionJoin}, "➕ Bobby joined\n"},
+		{queueAction{verb: actionFill}, "✅ The stack filled\n"},
+		{queueAction{verb: actionClose}, "🔒 The queue closed\n"},
+		{queueAction{user: alice, verb: "unknown"}, ""},
+	}
+	q := &queueState{aliases: map[string]string{bob.ID: "Bobby"}}
+	for _, tt := range tests {
+		t.Run(string(tt.action.verb), func(t *testing.T) {
+			if got := q.actionTextLocked(tt.action); got != tt.want {
+				t.Errorf("actionTextLocked() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+
+	q.feed = []queueAction{{user: alice, verb: actionJoin}, {verb: actionFill}}
+	if got, want := q.feedTextLocked(), "➕ alice joined\n✅ The stack filled\n"; got != want {
+		t.Errorf("feedTextLocked() = %q, want %q", got, want)
+	}
+}
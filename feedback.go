@@ -0,0 +1,70 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"runtime/debug"
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// FeedbackChannelID is where /feedback submissions are forwarded.
+var FeedbackChannelID = os.Getenv("STANDBY_FEEDBACK_CHANNEL_ID")
+
+const feedbackCooldown = 10 * time.Minute
+
+// version returns the VCS revision the bot was built from, if known.
+func version() string {
+	if info, ok := debug.ReadBuildInfo(); ok {
+		for _, setting := range info.Settings {
+			if setting.Key == "vcs.revision" {
+				return setting.Value
+			}
+		}
+	}
+	return "unknown"
+}
+
+func (q *queueState) handleFeedbackCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if FeedbackChannelID == "" {
+		respondEphemeral(s, i, tr("Feedback isn't set up for this bot."))
+		return
+	}
+	text := strings.TrimSpace(i.ApplicationCommandData().Options[0].StringValue())
+	userID := i.Member.User.ID
+
+	q.Lock()
+	defer q.Unlock()
+
+	if last, ok := q.lastFeedback[userID]; ok && time.Since(last) < feedbackCooldown {
+		respondEphemeral(s, i, tr("You can send more feedback <t:%d:R>.", last.Add(feedbackCooldown).Unix()))
+		return
+	}
+
+	_, err := sendMessage(s, FeedbackChannelID, &discordgo.MessageSend{
+		Embeds: []*discordgo.MessageEmbed{
+			{
+				Type:        discordgo.EmbedTypeRich,
+				Title:       "Feedback",
+				Color:       0x0099FF,
+				Description: text,
+				Fields: []*discordgo.MessageEmbedField{
+					{Name: "From", Value: fmt.Sprintf("<@%s> (%s)", userID, i.Member.User.Username), Inline: true},
+					{Name: "Guild", Value: i.GuildID, Inline: true},
+					{Name: "Version", Value: version(), Inline: true},
+				},
+			},
+		},
+		AllowedMentions: &discordgo.MessageAllowedMentions{},
+	})
+	if err != nil {
+		interactionLogger(i).Error("error forwarding feedback", "err", err)
+		respondEphemeral(s, i, tr("Couldn't send your feedback, please try again later."))
+		return
+	}
+	q.lastFeedback[userID] = time.Now()
+
+	respondEphemeral(s, i, tr("Thanks for the feedback!"))
+}
@@ -0,0 +1,171 @@
+package main
+
+import (
+	"cmp"
+	"fmt"
+	"net/http"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// maxFills is how many fill times are kept for /stats server.
+const maxFills = 500
+
+// fillBuckets are the upper bounds, in seconds, of the fill time histogram.
+var fillBuckets = []float64{60, 300, 600, 900, 1800, 3600, 7200}
+
+// queueFill is how long a queue for game opened at took to reach a full
+// stack.
+type queueFill struct {
+	At   time.Time
+	Took time.Duration
+	Game string
+}
+
+// fillHistogram counts fill times for /metrics since the bot started.
+type fillHistogram struct {
+	counts []uint64
+	count  uint64
+	sum    float64
+}
+
+// recordFillLocked records the fill time of the current queue, once per
+// queue.
+//
+// lock must be held
+func (q *queueState) recordFillLocked() {
+	if q.fillRecorded {
+		return
+	}
+	q.fillRecorded = true
+
+	took := time.Since(q.startTime)
+	q.fills = append(q.fills, queueFill{At: q.startTime, Took: took, Game: q.game})
+	if len(q.fills) > maxFills {
+		q.fills = q.fills[len(q.fills)-maxFills:]
+	}
+
+	if q.fillHist.counts == nil {
+		q.fillHist.counts = make([]uint64, len(fillBuckets))
+	}
+	for idx, bound := range fillBuckets {
+		if took.Seconds() <= bound {
+			q.fillHist.counts[idx]++
+		}
+	}
+	q.fillHist.count++
+	q.fillHist.sum += took.Seconds()
+}
+
+// handleMetrics serves the fill time histogram in the Prometheus text format.
+func (q *queueState) handleMetrics(w http.ResponseWriter, r *http.Request) {
+	q.Lock()
+	hist := q.fillHist
+	hist.counts = slices.Clone(hist.counts)
+	q.Unlock()
+
+	var sb strings.Builder
+	sb.WriteString("# HELP standby_queue_fill_seconds Time from opening a queue to a full stack.\n")
+	sb.WriteString("# TYPE standby_queue_fill_seconds histogram\n")
+	for idx, bound := range fillBuckets {
+		var n uint64
+		if hist.counts != nil {
+			n = hist.counts[idx]
+		}
+		sb.WriteString(fmt.Sprintf("standby_queue_fill_seconds_bucket{le=\"%g\"} %d\n", bound, n))
+	}
+	sb.WriteString(fmt.Sprintf("standby_queue_fill_seconds_bucket{le=\"+Inf\"} %d\n", hist.count))
+	sb.WriteString(fmt.Sprintf("standby_queue_fill_seconds_sum %g\n", hist.sum))
+	sb.WriteString(fmt.Sprintf("standby_queue_fill_seconds_count %d\n", hist.count))
+
+	w.Header().Set("Content-Type", "text/plain; version=0.0.4")
+	w.Write([]byte(sb.String()))
+}
+
+// fastestHours returns up to n hours of the day, in Timezone, whose queues
+// filled quickest on average.
+func fastestHours(fills []queueFill, n int) []string {
+	var total [24]time.Duration
+	var count [24]int
+	for _, f := range fills {
+		hour := f.At.In(Timezone).Hour()
+		total[hour] += f.Took
+		count[hour]++
+	}
+
+	var hours []int
+	for hour := range count {
+		if count[hour] > 0 {
+			hours = append(hours, hour)
+		}
+	}
+	avg := func(hour int) time.Duration {
+		return total[hour] / time.Duration(count[hour])
+	}
+	slices.SortFunc(hours, func(a, b int) int {
+		return cmp.Compare(avg(a), avg(b))
+	})
+
+	lines := make([]string, 0, n)
+	for _, hour := range hours[:min(n, len(hours))] {
+		lines = append(lines, fmt.Sprintf("%02d:00 — %s (%d)", hour, avg(hour).Round(time.Second), count[hour]))
+	}
+	return lines
+}
+
+func (q *queueState) handleServerStatsCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	var gameKey string
+	if opts := i.ApplicationCommandData().Options[0].Options; len(opts) > 0 {
+		gameKey = opts[0].StringValue()
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	fills := q.fills
+	title := tr("Server stats")
+	if gameKey != "" {
+		fills = nil
+		for _, f := range q.fills {
+			if f.Game == gameKey {
+				fills = append(fills, f)
+			}
+		}
+		if g, ok := q.knownGames[gameKey]; ok {
+			title = tr("Server stats for %s", g.Name)
+		}
+	}
+	if len(fills) == 0 {
+		respondEphemeral(s, i, tr("No queue has filled yet."))
+		return
+	}
+
+	took := make([]time.Duration, len(fills))
+	for idx, f := range fills {
+		took[idx] = f.Took
+	}
+	slices.Sort(took)
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{
+			Embeds: []*discordgo.MessageEmbed{
+				{
+					Type:  discordgo.EmbedTypeRich,
+					Title: title,
+					Color: 0x0099FF,
+					Fields: []*discordgo.MessageEmbedField{
+						{Name: tr("Queues filled"), Value: fmt.Sprintf("%d", len(took)), Inline: true},
+						{Name: tr("Median fill"), Value: took[len(took)/2].Round(time.Second).String(), Inline: true},
+						{Name: tr("Fastest fill"), Value: took[0].Round(time.Second).String(), Inline: true},
+						{Name: tr("Quickest hours (%s)", Timezone), Value: strings.Join(fastestHours(fills, 3), "\n")},
+					},
+				},
+			},
+			Flags: discordgo.MessageFlagsEphemeral,
+		},
+	})
+}
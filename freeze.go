@@ -0,0 +1,32 @@
+package main
+
+import "github.com/bwmarrin/discordgo"
+
+// frozenColor marks a queue whose roster is frozen.
+const frozenColor = 0xFFA500
+
+var frozenText = tr("**Paused:** joins and leaves are blocked until the queue is unpaused.\n")
+
+// handleFreezeLocked pauses or unpauses the queue, keeping the roster but
+// blocking joins and leaves while frozen.
+//
+// lock must be held
+func (q *queueState) handleFreezeLocked(s *discordgo.Session, i *discordgo.InteractionCreate, frozen bool) {
+	if i.Message.ID != q.currentMsgID {
+		respondEphemeral(s, i, tr("This queue is no longer open."))
+		return
+	}
+	if !q.canManageLocked(s, i) {
+		respondEphemeral(s, i, tr("Only admins or the queue creator can pause the queue."))
+		return
+	}
+	q.frozen = frozen
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseUpdateMessage,
+		Data: &discordgo.InteractionResponseData{
+			Embeds:     []*discordgo.MessageEmbed{q.queueEmbedLocked()},
+			Components: q.queueComponentsLocked(),
+		},
+	})
+}
@@ -0,0 +1,141 @@
+package main
+
+import (
+	"os"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// GameRoles are the in-game roles players can pick after joining, such as
+// "Tank,DPS,Support". The picker is only shown when some are configured.
+var GameRoles = splitList(os.Getenv("STANDBY_GAME_ROLES"))
+
+// maxGameRoles is how many options fit in a select menu.
+const maxGameRoles = 25
+
+// gameRolePickerLocked is the select menu sent to a user after they join.
+//
+// lock must be held
+func (q *queueState) gameRolePickerLocked(userID string) []discordgo.MessageComponent {
+	picked := map[string]bool{}
+	for _, r := range q.gameRoles[userID] {
+		picked[r] = true
+	}
+	choices := GameRoles[:min(len(GameRoles), maxGameRoles)]
+	options := make([]discordgo.SelectMenuOption, len(choices))
+	for idx, r := range choices {
+		options[idx] = discordgo.SelectMenuOption{
+			Label:   r,
+			Value:   r,
+			Default: picked[r],
+		}
+	}
+	minValues := 0
+	return []discordgo.MessageComponent{
+		discordgo.ActionsRow{
+			Components: []discordgo.MessageComponent{
+				discordgo.SelectMenu{
+					MenuType:    discordgo.StringSelectMenu,
+					CustomID:    "pick_game_role",
+					Placeholder: tr("Pick the roles you can play"),
+					MinValues:   &minValues,
+					MaxValues:   len(choices),
+					Options:     options,
+				},
+			},
+		},
+	}
+}
+
+// handlePickGameRoleLocked stores the roles a user picked and shows them in
+// the queue.
+//
+// lock must be held
+func (q *queueState) handlePickGameRoleLocked(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	userID := i.Member.User.ID
+	picked := i.MessageComponentData().Values
+	if len(picked) == 0 {
+		delete(q.gameRoles, userID)
+	} else {
+		q.gameRoles[userID] = picked
+	}
+	if q.positionLocked(userID) > 0 {
+		q.scheduleRefreshLocked(s)
+	}
+
+	content := tr("Your roles were cleared.")
+	if len(picked) > 0 {
+		content = tr("You'll be shown as %s.", strings.Join(picked, "/"))
+	}
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseUpdateMessage,
+		Data: &discordgo.InteractionResponseData{
+			Content:    content,
+			Components: q.gameRolePickerLocked(userID),
+		},
+	})
+}
+
+// gameRolesTextLocked renders a user's picked roles for the queue embed, or
+// "" if they haven't picked any.
+//
+// lock must be held
+func (q *queueState) gameRolesTextLocked(userID string) string {
+	if len(GameRoles) == 0 || len(q.gameRoles[userID]) == 0 {
+		return ""
+	}
+	return " [" + strings.Join(q.gameRoles[userID], "/") + "]"
+}
+
+// followupJoinedLocked tells a user who just joined where they are in the
+// queue, along with the role picker if GameRoles are configured.
+//
+// lock must be held
+func (q *queueState) followupJoinedLocked(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	content := q.positionTextLocked(i.Member.User.ID)
+	if len(GameRoles) == 0 {
+		followupEphemeral(s, i, content)
+		return
+	}
+	if _, err := s.FollowupMessageCreate(i.Interaction, false, &discordgo.WebhookParams{
+		Content:    content,
+		Components: q.gameRolePickerLocked(i.Member.User.ID),
+		Flags:      discordgo.MessageFlagsEphemeral,
+	}); err != nil {
+		interactionLogger(i).Error("error sending role picker", "err", err)
+	}
+}
+
+// respondRolePickerLocked answers an interaction with content and, if
+// GameRoles are configured, the role picker for the user who sent it.
+//
+// lock must be held
+func (q *queueState) respondRolePickerLocked(s *discordgo.Session, i *discordgo.InteractionCreate, content string) {
+	if len(GameRoles) == 0 {
+		respondEphemeral(s, i, content)
+		return
+	}
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{
+			Content:    content,
+			Components: q.gameRolePickerLocked(i.Member.User.ID),
+			Flags:      discordgo.MessageFlagsEphemeral,
+		},
+	})
+}
+
+// unpickedLocked returns the players in stack who haven't picked any roles,
+// e.g. because someone else added them to the queue.
+//
+// lock must be held
+func (q *queueState) unpickedLocked(stack []*discordgo.User) []*discordgo.User {
+	var unpicked []*discordgo.User
+	for _, user := range stack {
+		if len(q.gameRoles[user.ID]) == 0 && !isSimulated(user.ID) {
+			unpicked = append(unpicked, user)
+		}
+	}
+	return unpicked
+}
@@ -0,0 +1,149 @@
+package main
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// game themes the queue embed for a particular game.
+type game struct {
+	Name      string `json:"name"`
+	Color     int    `json:"color,omitempty"`
+	Thumbnail string `json:"thumbnail,omitempty"`
+	// Size replaces the preset's stack size when /standby names the game
+	// but no preset.
+	Size int `json:"size,omitempty"`
+}
+
+// builtinGames are the games every guild starts with, keyed by the value of
+// the /standby game option. They can be replaced with /standby-game.
+func builtinGames() map[string]game {
+	return map[string]game{
+		"valorant": {Name: "Valorant", Color: 0xFF4655},
+		"cs2":      {Name: "Counter-Strike 2", Color: 0xDE9B35},
+		"league":   {Name: "League of Legends", Color: 0xC89B3C},
+	}
+}
+
+// gameKey is how a game is referred to by the /standby game option.
+func gameKey(name string) string {
+	return strings.Join(strings.Fields(strings.ToLower(name)), "-")
+}
+
+// check returns why the game couldn't be saved with /standby-game, or "" if
+// it could.
+func (g game) check() string {
+	switch {
+	case gameKey(g.Name) == "":
+		return tr("Game name cannot be empty.")
+	case g.Size != 0 && (g.Size < 2 || g.Size > 25):
+		return tr("Game **%s** needs a size between 2 and 25.", g.Name)
+	case g.Color < 0 || g.Color > 0xFFFFFF:
+		return tr("Use a hex color like #FF7518.")
+	}
+	return ""
+}
+
+// title is the queue embed title for the game.
+func (g game) title() string {
+	return fmt.Sprintf("%s Standby Queue", g.Name)
+}
+
+func (q *queueState) handleGameCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+	sub := i.ApplicationCommandData().Options[0]
+
+	q.Lock()
+	defer q.Unlock()
+
+	switch sub.Name {
+	case "set":
+		var g game
+		for _, opt := range sub.Options {
+			switch opt.Name {
+			case "name":
+				g.Name = strings.TrimSpace(opt.StringValue())
+			case "icon":
+				g.Thumbnail = strings.TrimSpace(opt.StringValue())
+			case "size":
+				g.Size = int(opt.IntValue())
+			case "color":
+				c, ok := parseColor(opt.StringValue())
+				if !ok {
+					respondEphemeral(s, i, tr("Use a hex color like #FF7518."))
+					return
+				}
+				g.Color = c
+			}
+		}
+		if reason := g.check(); reason != "" {
+			respondEphemeral(s, i, reason)
+			return
+		}
+		key := gameKey(g.Name)
+		q.knownGames[key] = g
+		respondEphemeral(s, i, tr("Saved game **%s**. Open a queue for it with `/standby game:%s`.", g.Name, key))
+
+	case "delete":
+		key := sub.Options[0].StringValue()
+		g, ok := q.knownGames[key]
+		if !ok {
+			respondEphemeral(s, i, tr("There is no game named **%s**.", key))
+			return
+		}
+		delete(q.knownGames, key)
+		respondEphemeral(s, i, tr("Deleted game **%s**.", g.Name))
+
+	case "list":
+		keys := make([]string, 0, len(q.knownGames))
+		for key := range q.knownGames {
+			keys = append(keys, key)
+		}
+		sort.Strings(keys)
+		var sb strings.Builder
+		for _, key := range keys {
+			g := q.knownGames[key]
+			sb.WriteString(fmt.Sprintf("**%s** (`%s`)", g.Name, key))
+			if g.Size > 0 {
+				sb.WriteString(fmt.Sprintf(", %d players", g.Size))
+			}
+			sb.WriteString("\n")
+		}
+		if sb.Len() == 0 {
+			respondEphemeral(s, i, tr("There are no games."))
+			return
+		}
+		respondEphemeral(s, i, sb.String())
+	}
+}
+
+// handleGameAutocomplete suggests games whose name matches what has been
+// typed so far.
+func (q *queueState) handleGameAutocomplete(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	typed := strings.ToLower(focusedOption(i.ApplicationCommandData().Options).StringValue())
+
+	q.Lock()
+	var choices []*discordgo.ApplicationCommandOptionChoice
+	for key, g := range q.knownGames {
+		if strings.Contains(strings.ToLower(g.Name), typed) || strings.Contains(key, typed) {
+			choices = append(choices, &discordgo.ApplicationCommandOptionChoice{Name: g.Name, Value: key})
+		}
+	}
+	q.Unlock()
+	sort.Slice(choices, func(a, b int) bool {
+		return choices[a].Name < choices[b].Name
+	})
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionApplicationCommandAutocompleteResult,
+		Data: &discordgo.InteractionResponseData{
+			Choices: choices[:min(len(choices), 25)],
+		},
+	})
+}
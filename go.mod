@@ -1,6 +1,6 @@
 module discord-standby-bot
 
-go 1.20
+go 1.21
 
 require github.com/bwmarrin/discordgo v0.27.2-0.20240104191117-afc57886f91a
 
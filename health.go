@@ -0,0 +1,84 @@
+package main
+
+import (
+	"net/http"
+	"sync/atomic"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// maxHeartbeatLatency is the gateway heartbeat round trip above which the
+// bot reports itself unhealthy.
+const maxHeartbeatLatency = 5 * time.Second
+
+// serving is set once commands and handlers are registered, and cleared
+// again when the bot starts shutting down.
+var serving atomic.Bool
+
+type healthResponse struct {
+	Healthy   bool   `json:"healthy"`
+	Gateway   string `json:"gateway"`
+	LatencyMS int64  `json:"latency_ms"`
+	Error     string `json:"error,omitempty"`
+}
+
+// gatewayHealth reports whether the gateway is connected and keeping up
+// with heartbeats.
+func gatewayHealth(s *discordgo.Session) healthResponse {
+	s.RLock()
+	connected := s.DataReady
+	s.RUnlock()
+	latency := s.HeartbeatLatency()
+
+	resp := healthResponse{
+		Healthy:   true,
+		Gateway:   "connected",
+		LatencyMS: latency.Milliseconds(),
+	}
+	switch {
+	case !connected:
+		resp.Gateway = "disconnected"
+		resp.Error = "gateway is not connected"
+	case latency > maxHeartbeatLatency:
+		resp.Error = "gateway heartbeat is lagging"
+	}
+	resp.Healthy = resp.Error == ""
+	return resp
+}
+
+// handleHealth serves /healthz, which unlike /api/status fails with 503
+// when the gateway is disconnected or lagging.
+func handleHealth(s *discordgo.Session) http.HandlerFunc {
+	return func(w http.ResponseWriter, r *http.Request) {
+		resp := gatewayHealth(s)
+		status := http.StatusOK
+		if !resp.Healthy {
+			status = http.StatusServiceUnavailable
+		}
+		writeJSON(w, status, resp)
+	}
+}
+
+// handleLive serves /live, which only shows the process is up. A failing
+// liveness probe means the bot should be restarted.
+func handleLive(w http.ResponseWriter, r *http.Request) {
+	writeJSON(w, http.StatusOK, map[string]bool{"live": true})
+}
+
+// handleReady serves /ready, which fails until the bot is set up and again
+// once it starts shutting down, so no traffic is routed to it meanwhile.
+func handleReady(s *discordgo.Session) http.HandlerFunc {
+	return func(w http.ResponseWriter, r *http.Request) {
+		resp := gatewayHealth(s)
+		if resp.Healthy && !serving.Load() {
+			resp.Healthy = false
+			resp.Error = "bot is not serving"
+		}
+		status := http.StatusOK
+		if !resp.Healthy {
+			status = http.StatusServiceUnavailable
+		}
+		writeJSON(w, status, resp)
+	}
+}
@@ -0,0 +1,45 @@
+package main
+
+import (
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// maxHistory is how many queue events are kept for /history.
+const maxHistory = 100
+
+// lock must be held
+func (q *queueState) recordHistoryLocked(action queueAction) {
+	q.history = append(q.history, action)
+	if len(q.history) > maxHistory {
+		q.history = q.history[len(q.history)-maxHistory:]
+	}
+}
+
+// lock must be held
+func (q *queueState) recordEventLocked(verb actionVerb) {
+	q.recordHistoryLocked(queueAction{verb: verb, at: time.Now()})
+}
+
+func (q *queueState) handleHistoryCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	count := 10
+	if opts := i.ApplicationCommandData().Options; len(opts) > 0 {
+		count = int(opts[0].IntValue())
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if len(q.history) == 0 {
+		respondEphemeral(s, i, tr("Nothing has happened in the queue yet."))
+		return
+	}
+	events := q.history[max(len(q.history)-count, 0):]
+	var sb strings.Builder
+	for _, action := range events {
+		sb.WriteString(tr("<t:%d:T> %s", action.at.Unix(), q.actionTextLocked(action)))
+	}
+	respondEphemeral(s, i, sb.String())
+}
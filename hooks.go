@@ -0,0 +1,94 @@
+package main
+
+import (
+	"fmt"
+	"log/slog"
+	"os"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// QueueHook lets compiled-in extensions react to queue events without
+// changing the handlers. Hooks run with the queue lock held, so they must
+// not call back into queueState.
+type QueueHook interface {
+	// OnJoin is called after a user is added to the queue.
+	OnJoin(s *discordgo.Session, user *discordgo.User)
+	// OnLeave is called after a user leaves or is removed from the queue.
+	OnLeave(s *discordgo.Session, user *discordgo.User)
+	// OnFull is called with the stack when the ready notification is sent.
+	OnFull(s *discordgo.Session, users []*discordgo.User)
+	// OnClose is called with the remaining users when the queue closes.
+	OnClose(s *discordgo.Session, users []*discordgo.User)
+}
+
+var hookFactories = map[string]func() QueueHook{}
+
+// RegisterHook makes a hook available to enable by name through
+// STANDBY_HOOKS. Extensions call it from an init function.
+func RegisterHook(name string, factory func() QueueHook) {
+	hookFactories[name] = factory
+}
+
+// enabledHooks builds the hooks listed in the comma-separated STANDBY_HOOKS,
+// plus the watcher role hook when a role is configured.
+func enabledHooks() []QueueHook {
+	var hooks []QueueHook
+	if WatcherRoleID != "" {
+		hooks = append(hooks, watcherRoleHook{})
+	}
+	for _, name := range strings.Split(os.Getenv("STANDBY_HOOKS"), ",") {
+		name = strings.TrimSpace(name)
+		if name == "" {
+			continue
+		}
+		factory, ok := hookFactories[name]
+		if !ok {
+			panic(fmt.Sprintf("unknown hook %q in STANDBY_HOOKS", name))
+		}
+		hooks = append(hooks, factory())
+	}
+	return hooks
+}
+
+// watcherRoleHook grants WatcherRoleID to users while they are queued, until
+// they leave or their stack launches.
+type watcherRoleHook struct{}
+
+func (watcherRoleHook) OnJoin(s *discordgo.Session, user *discordgo.User) {
+	setWatcherRole(s, user.ID, true)
+}
+
+func (watcherRoleHook) OnLeave(s *discordgo.Session, user *discordgo.User) {
+	setWatcherRole(s, user.ID, false)
+}
+
+// OnFull takes the role back from the launched stack, since they are no
+// longer waiting for a game.
+func (watcherRoleHook) OnFull(s *discordgo.Session, users []*discordgo.User) {
+	for _, user := range users {
+		setWatcherRole(s, user.ID, false)
+	}
+}
+
+func (watcherRoleHook) OnClose(s *discordgo.Session, users []*discordgo.User) {
+	for _, user := range users {
+		setWatcherRole(s, user.ID, false)
+	}
+}
+
+func setWatcherRole(s *discordgo.Session, userID string, queued bool) {
+	if isSimulated(userID) {
+		return
+	}
+	var err error
+	if queued {
+		err = s.GuildMemberRoleAdd(GuildID, userID, WatcherRoleID)
+	} else {
+		err = s.GuildMemberRoleRemove(GuildID, userID, WatcherRoleID)
+	}
+	if err != nil {
+		slog.Error("error updating watcher role", "err", err, "user", userID)
+	}
+}
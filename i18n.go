@@ -0,0 +1,242 @@
+package main
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// Locale picks the catalog bot messages are translated with. Messages
+// missing from it, or an unknown locale, fall back to English.
+var Locale = os.Getenv("STANDBY_LOCALE")
+
+// catalogs maps a locale to translations keyed by the English format string.
+var catalogs = map[string]map[string]string{
+	"ko": {
+		" They have %s to accept the spot, or it goes to the next person.": " %s 안에 자리를 수락하지 않으면 다음 사람에게 넘어갑니다.",
+		" This replaces the queue that was scheduled for <t:%d:t>.":        " <t:%d:t>에 예약된 대기열을 대체합니다.",
+		" — joined <t:%d:R>":                            " — <t:%d:R> 참가",
+		" — waiting to accept":                          " — 수락 대기 중",
+		"### Queued users (%d):\n":                      "### 대기 중인 사용자 (%d):\n",
+		"### Spectators (%d):\n":                        "### 관전자 (%d):\n",
+		"### Subs (%d):\n":                              "### 대타 (%d):\n",
+		"%d slots reserved for <@&%s> until <t:%d:t>\n": "<t:%[3]d:t>까지 <@&%[2]s> 전용 자리 %[1]d개\n",
+		"%dW %dL":                                                              "%d승 %d패",
+		"%s didn't answer in time, so the spot went to the next person.":       "%s 님이 제때 답하지 않아 자리가 다음 사람에게 넘어갔습니다.",
+		"%s dropped out of the stack. Subs, click Join to take their spot: %s": "%s 님이 스택에서 빠졌습니다. 대타 여러분, 참가를 눌러 자리를 채워 주세요: %s",
+		"%s is already in the queue.":                                          "%s 님은 이미 대기열에 있습니다.",
+		"%s joined the server too recently to queue. They can join <t:%d:R>.":  "%s 님은 서버에 들어온 지 얼마 되지 않아 참가할 수 없습니다. <t:%d:R>부터 참가할 수 있습니다.",
+		"%s passed on the spot.":                                               "%s 님이 자리를 넘겼습니다.",
+		"%s picks":                                                             "%s 님의 선택",
+		"%s took the spot.":                                                    "%s 님이 자리를 맡았습니다.",
+		"%s was promoted into the stack. %s moved to the top of the waitlist.": "%s 님이 스택으로 올라갔습니다. %s 님은 대기 명단 맨 앞으로 이동했습니다.",
+		"%s's account is too new to queue. They can join <t:%d:R>.":            "%s 님의 계정은 너무 새로워 참가할 수 없습니다. <t:%d:R>부터 참가할 수 있습니다.",
+		"%s, a spot opened up in the stack! Accept it <t:%d:R> or it goes to the next person.": "%s 님, 스택에 자리가 났습니다! <t:%d:R>까지 수락하지 않으면 다음 사람에게 넘어갑니다.",
+		"%s\n### Map veto\n%s, ban a map.":                                                     "%s\n### 맵 밴\n%s 님, 밴할 맵을 고르세요.",
+		"%s\n### Map: %s":                                                                      "%s\n### 맵: %s",
+		"**Game:** %s\n":                                                                       "**게임:** %s\n",
+		"**New to the standby queue?**\n- The first %[1]d users make up the stack. Anyone after that is on the waitlist.\n- %[2]s\n- Once %[1]d users are queued, the stack is pinged in this channel. You're not pinged for joins or leaves.\n- Click Leave if you can no longer play so the waitlist can move up.": "**대기열이 처음이신가요?**\n- 먼저 들어온 %[1]d명이 스택이 됩니다. 그 뒤로는 대기 명단입니다.\n- %[2]s\n- %[1]d명이 모이면 이 채널에서 스택에 알림이 갑니다. 참가나 나가기로는 알림이 가지 않습니다.\n- 더 이상 플레이할 수 없으면 나가기를 눌러 대기 명단이 올라올 수 있게 해 주세요.",
+		"**Paused:** joins and leaves are blocked until the queue is unpaused.\n":                                                                                                                                                                                                                                    "**일시 정지:** 대기열이 재개될 때까지 참가와 나가기가 막혀 있습니다.\n",
+		"<@%s> is already in the stack.":                                                                                                                                                                                                                                                                             "<@%s> 님은 이미 스택에 있습니다.",
+		"<@%s> is not in the queue.":                                                                                                                                                                                                                                                                                 "<@%s> 님은 대기열에 없습니다.",
+		"<@&%s> the queue has been at %d/%d for %s. One more player is needed!":                                                                                                                                                                                                                                      "<@&%s> 대기열이 %[4]s 동안 %[2]d/%[3]d에 머물러 있습니다. 한 명만 더 오면 됩니다!",
+		"Accept":                                          "수락",
+		"Added %d simulated users.":                       "가상 사용자 %d명을 추가했습니다.",
+		"Added <@%s> to the queue as #%d.":                "<@%s> 님을 대기열 #%d번으로 추가했습니다.",
+		"Alias cannot be empty.":                          "별명은 비워 둘 수 없습니다.",
+		"Alias cleared.":                                  "별명을 지웠습니다.",
+		"Attach a backup file made with /standby-backup.": "/standby-backup으로 만든 백업 파일을 첨부하세요.",
+		"Avg. wait":                                                "평균 대기",
+		"Bots can't join the queue.":                               "봇은 대기열에 참가할 수 없습니다.",
+		"Cancelled the queue scheduled for <t:%d:t>.":              "<t:%d:t>에 예약된 대기열을 취소했습니다.",
+		"Captains Draft":                                           "주장 드래프트",
+		"Close":                                                    "닫기",
+		"Closing queue.":                                           "대기열을 닫습니다.",
+		"Couldn't create a backup.":                                "백업을 만들지 못했습니다.",
+		"Couldn't create a thread for the queue.":                  "대기열 스레드를 만들지 못했습니다.",
+		"Couldn't export the queue.":                               "대기열을 내보내지 못했습니다.",
+		"Couldn't find that event.":                                "해당 이벤트를 찾을 수 없습니다.",
+		"Couldn't find the Riot ID `%s`. Use the Name#TAG format.": "Riot ID `%s`을(를) 찾지 못했습니다. 이름#태그 형식을 사용하세요.",
+		"Couldn't open a queue.":                                   "대기열을 열지 못했습니다.",
+		"Couldn't read that backup file.":                          "백업 파일을 읽지 못했습니다.",
+		"Couldn't restore that backup: %s":                         "백업을 복원할 수 없습니다: %s",
+		"Couldn't send your feedback, please try again later.":     "피드백을 보내지 못했습니다. 나중에 다시 시도해 주세요.",
+		"Decline":                "거절",
+		"Deleted game **%s**.":   "**%s** 게임을 삭제했습니다.",
+		"Deleted preset **%s**.": "**%s** 프리셋을 삭제했습니다.",
+		"Discord is having issues. Your click is saved and the queue will update once it recovers.": "Discord에 문제가 있습니다. 클릭은 저장되었으며 복구되면 대기열이 업데이트됩니다.",
+		"Draft":                                                      "드래프트",
+		"Fastest fill":                                               "가장 빠른 채움",
+		"Feedback isn't set up for this bot.":                        "이 봇에는 피드백이 설정되어 있지 않습니다.",
+		"Game **%s** is listed under **%s**.":                        "**%s** 게임이 **%s** 키로 저장되어 있습니다.",
+		"Game **%s** needs a size between 2 and 25.":                 "**%s** 게임의 인원은 2명에서 25명 사이여야 합니다.",
+		"Game name cannot be empty.":                                 "게임 이름은 비워 둘 수 없습니다.",
+		"Game":                                                       "게임",
+		"Game: %s\n":                                                 "게임: %s\n",
+		"Games":                                                      "게임 수",
+		"In-game ID removed.":                                        "게임 내 ID를 삭제했습니다.",
+		"In-game ID set to `%s`.":                                    "게임 내 ID를 `%s`(으)로 설정했습니다.",
+		"It's %s's turn to ban.":                                     "%s 님이 밴할 차례입니다.",
+		"It's %s's turn to pick.":                                    "%s 님이 고를 차례입니다.",
+		"Join":                                                       "참가",
+		"Joined with %s. %s":                                         "%s 님과 함께 참가했습니다. %s",
+		"Leave":                                                      "나가기",
+		"Linked Riot ID `%s`. Your rank will be shown in the queue.": "Riot ID `%s`을(를) 연결했습니다. 대기열에 랭크가 표시됩니다.",
+		"Lobby code from %s for your %s game: `%s`":                  "%s 님이 보낸 %s 게임 로비 코드: `%s`",
+		"Lobby code":                                                 "로비 코드",
+		"Median fill":                                                "채워지는 시간 중앙값",
+		"Need %d players to draft teams.":                            "팀 드래프트에는 %d명이 필요합니다.",
+		"Need %d players to split teams.":                            "팀을 나누려면 %d명이 필요합니다.",
+		"No active queue to close.":                                  "닫을 대기열이 없습니다.",
+		"No queue has filled yet.":                                   "아직 찬 대기열이 없습니다.",
+		"No-shows":                                                   "불참",
+		"Notes":                                                      "메모",
+		"Nothing has happened in the queue yet.":                     "아직 대기열에 기록된 일이 없습니다.",
+		"Notice will be shown on the next queue message.":            "다음 대기열 메시지에 공지가 표시됩니다.",
+		"Only %d spots are left in the stack, so your party of %d would be split onto the waitlist.": "스택에 남은 자리가 %d개뿐이라 %d명 파티가 대기 명단으로 나뉘게 됩니다.",
+		"Only admins can use this command.":                                                          "관리자만 이 명령어를 사용할 수 있습니다.",
+		"Only admins or the queue creator can pause the queue.":                                      "관리자나 대기열을 연 사람만 대기열을 일시 정지할 수 있습니다.",
+		"Only admins or the queue creator can post the lobby code.":                                  "관리자나 대기열을 연 사람만 로비 코드를 보낼 수 있습니다.",
+		"Only admins or the queue creator can use this command.":                                     "관리자나 대기열을 연 사람만 이 명령어를 사용할 수 있습니다.",
+		"Only players from the last stack or admins can record its result.":                          "마지막 스택의 플레이어나 관리자만 결과를 기록할 수 있습니다.",
+		"Only the queue creator, moderators or admins can do that.":                                  "대기열을 연 사람, 모더레이터나 관리자만 할 수 있습니다.",
+		"Open standby queue":                                                                         "대기열 열기",
+		"Open the queue in a new thread":                                                             "새 스레드에서 대기열 열기",
+		"Open":                                                                                       "열기",
+		"Pause":                                                                                      "일시 정지",
+		"Pick at least one friend to queue with, or use the Join button.":                            "함께 참가할 친구를 한 명 이상 고르거나 참가 버튼을 사용하세요.",
+		"Pick the roles you can play":                                                                "플레이할 수 있는 역할을 고르세요",
+		"Pick the roles you can play.":                                                               "플레이할 수 있는 역할을 고르세요.",
+		"Pick two different users.":                                                                  "서로 다른 사용자 두 명을 고르세요.",
+		"Post lobby code":                                                                            "로비 코드 보내기",
+		"Preset **%s** is listed under **%s**.":                                                      "**%s** 프리셋이 **%s** 이름으로 저장되어 있습니다.",
+		"Preset **%s** needs a size between 2 and 25.":                                               "**%s** 프리셋의 인원은 2명에서 25명 사이여야 합니다.",
+		"Preset **%s** needs a team size between 1 and 12.":                                          "**%s** 프리셋의 팀 인원은 1명에서 12명 사이여야 합니다.",
+		"Preset **%s** needs an alert time.":                                                         "**%s** 프리셋에 알림 시간이 필요합니다.",
+		"Preset name cannot be empty.":                                                               "프리셋 이름은 비워 둘 수 없습니다.",
+		"Preset: %s\n":                                                                               "프리셋: %s\n",
+		"Promoted <@%s>.":                                                                            "<@%s> 님을 승격했습니다.",
+		"Queue in <#%s> is full — %d/%d, time for a game!":                                           "<#%s> 대기열이 찼습니다 — %d/%d, 게임할 시간입니다!",
+		"Queue is closed":                                                                            "대기열이 닫혔습니다",
+		"Queue is open for **%s** starting <t:%d:R>! %s":                                             "<t:%[2]d:R>에 시작하는 **%[1]s** 대기열이 열렸습니다! %[3]s",
+		"Queue open in <#%s> — %d/%d":                                                                "<#%s>에서 대기열이 열렸습니다 — %d/%d",
+		"Queue opened <t:%d:R>\n":                                                                    "대기열 시작 <t:%d:R>\n",
+		"Queue will open <t:%d:R> for **%s**.":                                                       "**%[2]s** 대기열이 <t:%[1]d:R> 열립니다.",
+		"Queue will open <t:%d:t> (<t:%d:R>). Use /standby-close to cancel.":                         "대기열이 <t:%d:t>(<t:%d:R>)에 열립니다. 취소하려면 /standby-close를 사용하세요.",
+		"Queued":                                        "참가 횟수",
+		"Queues filled":                                 "채워진 대기열",
+		"Quickest hours (%s)":                           "가장 빨리 차는 시간대 (%s)",
+		"Rating":                                        "레이팅",
+		"Ready ping: %s\n":                              "준비 알림: %s\n",
+		"Record":                                        "전적",
+		"Recorded a **%s** (%s) for %s.":                "%[3]s 님의 **%[1]s** (%[2]s) 결과를 기록했습니다.",
+		"Recorded a **%s** for %s.":                     "%[2]s 님의 **%[1]s** 결과를 기록했습니다.",
+		"Remaining: %s":                                 "남은 인원: %s",
+		"Remove a user from the queue":                  "대기열에서 사용자 제거",
+		"Removed <@%s> from the queue.":                 "<@%s> 님을 대기열에서 제거했습니다.",
+		"Reopen with waitlist (%d)":                     "대기 명단과 함께 다시 열기 (%d)",
+		"Restored the backup from <t:%d:f>.":            "<t:%d:f>의 백업을 복원했습니다.",
+		"Riot account unlinked.":                        "Riot 계정 연결을 해제했습니다.",
+		"Riot accounts can't be linked on this server.": "이 서버에서는 Riot 계정을 연결할 수 없습니다.",
+		"Roles":                                                           "역할",
+		"Saved game **%s**. Open a queue for it with `/standby game:%s`.": "**%s** 게임을 저장했습니다. `/standby game:%s`로 대기열을 여세요.",
+		"Saved preset **%s**: %s":                                         "**%s** 프리셋을 저장했습니다: %s",
+		"Sent the lobby code to the %d players in the stack.":             "스택의 플레이어 %d명에게 로비 코드를 보냈습니다.",
+		"Sent the lobby code, but couldn't DM %s. They may have DMs turned off.": "로비 코드를 보냈지만 %s 님에게는 DM을 보내지 못했습니다. DM이 꺼져 있을 수 있습니다.",
+		"Server stats for %s":                                                    "%s 서버 통계",
+		"Server stats":                                                           "서버 통계",
+		"Shuffled the queue.":                                                    "대기열 순서를 섞었습니다.",
+		"Slow down! Try again in %ds.":                                           "너무 빠릅니다! %d초 후에 다시 시도하세요.",
+		"Slow down! Try again in a moment.":                                      "너무 빠릅니다! 잠시 후 다시 시도하세요.",
+		"Spectate":                                                               "관전",
+		"Split Teams":                                                            "팀 나누기",
+		"Starting queue.":                                                        "대기열을 시작합니다.",
+		"Stats for %s":                                                           "%s 님의 통계",
+		"Sub":                                                                    "대타",
+		"Swapped <@%s> (now #%d) and <@%s> (now #%d).":                           "<@%s> 님(현재 #%d)과 <@%s> 님(현재 #%d)의 순서를 바꿨습니다.",
+		"Team %d":                                          "%d팀",
+		"Team %s":                                          "%s 팀",
+		"Teams":                                            "팀",
+		"Thanks for the feedback!":                         "피드백 감사합니다!",
+		"That spot has already been filled.":               "이미 채워진 자리입니다.",
+		"The %s notification is back to the default text.": "%s 알림이 기본 문구로 돌아갔습니다.",
+		"The %s notification will now read:\n%s":           "이제 %s 알림은 다음과 같습니다:\n%s",
+		"The bot is busy, please try again in a moment.":   "봇이 바쁩니다. 잠시 후 다시 시도하세요.",
+		"The queue is paused.":                             "대기열이 일시 정지되었습니다.",
+		"The stack has already been pinged, so the queue can't be shuffled.": "스택 알림이 이미 나가서 대기열을 섞을 수 없습니다.",
+		"The stack is full but needs %s. %s, pick your roles with the Roles button on the queue, or an admin can /standby-promote a waitlisted player.": "스택이 찼지만 %s이(가) 필요합니다. %s 님, 대기열의 역할 버튼으로 역할을 고르거나, 관리자가 /standby-promote로 대기 명단의 플레이어를 올릴 수 있습니다.",
+		"The stack is full but needs %s. An admin can /standby-promote a waitlisted player who plays it.": "스택이 찼지만 %s이(가) 필요합니다. 관리자가 /standby-promote로 해당 역할을 하는 대기 명단의 플레이어를 올릴 수 있습니다.",
+		"There are enough users for a game!":                                                              "게임할 인원이 모였습니다!",
+		"There are no games.":                                                                             "게임이 없습니다.",
+		"There are no presets.":                                                                           "프리셋이 없습니다.",
+		"There is already an existing queue.":                                                             "이미 열린 대기열이 있습니다.",
+		"There is no finished stack to record a result for.":                                              "결과를 기록할 완료된 스택이 없습니다.",
+		"There is no game named **%s**.":                                                                  "**%s** 게임이 없습니다.",
+		"There is no open queue. Use /standby to start one.":                                              "열린 대기열이 없습니다. /standby로 시작하세요.",
+		"There is no preset named **%s**.":                                                                "**%s** 프리셋이 없습니다.",
+		"This draft is no longer active.":                                                                 "이 드래프트는 더 이상 진행 중이 아닙니다.",
+		"This map veto is over.":                                                                          "이 맵 밴이 끝났습니다.",
+		"This offer isn't for you or has expired.":                                                        "본인에게 온 제안이 아니거나 만료되었습니다.",
+		"This queue has been idle and will close <t:%d:R> unless someone joins or leaves.": "대기열에 활동이 없어 누군가 참가하거나 나가지 않으면 <t:%d:R>에 닫힙니다.",
+		"This queue is no longer open.":                                                    "이 대기열은 더 이상 열려 있지 않습니다.",
+		"This stack is no longer ready.":                                                   "이 스택은 더 이상 준비 상태가 아닙니다.",
+		"Title":                                                                            "제목",
+		"Unpause":                                                                          "재개",
+		"Use a 24-hour time like 20:30 (%s).":                                              "20:30 같은 24시간 형식으로 입력하세요 (%s).",
+		"Use a duration like 30m or 2h, up to %s.":                                         "30m이나 2h 같은 기간을 입력하세요 (최대 %s).",
+		"Use a hex color like #FF7518.":                                                    "#FF7518 같은 16진수 색상을 입력하세요.",
+		"User to remove":                                                                   "제거할 사용자",
+		"When someone in the stack leaves, a random waitlisted user is promoted into their slot.": "스택에서 누군가 나가면 무작위 대기자가 그 자리로 올라갑니다.",
+		"When someone in the stack leaves, the first waitlisted user with the priority role is promoted into their slot, or the longest waiting if nobody has it.": "스택에서 누군가 나가면 우선 역할이 있는 첫 대기자가 그 자리로 올라갑니다. 아무도 없으면 가장 오래 기다린 사람이 올라갑니다.",
+		"When someone in the stack leaves, the longest waiting user is promoted into their slot.": "스택에서 누군가 나가면 가장 오래 기다린 대기자가 그 자리로 올라갑니다.",
+		"When someone in the stack leaves, the waitlisted user who has been in the fewest stacks tonight is promoted into their slot.": "스택에서 누군가 나가면 오늘 밤 스택에 가장 적게 들어간 대기자가 그 자리로 올라갑니다.",
+		"You can send more feedback <t:%d:R>.":                                                                                         "<t:%d:R>에 피드백을 더 보낼 수 있습니다.",
+		"You will be shown as **%s** in the queue.":                                                                                    "대기열에 **%s**(으)로 표시됩니다.",
+		"You won't be pinged when the stack is ready until <t:%d:t>.":                                                                  "<t:%d:t>까지 스택이 준비되어도 알림을 받지 않습니다.",
+		"You'll be pinged when the stack is ready again.":                                                                              "이제 스택이 준비되면 다시 알림을 받습니다.",
+		"You'll be shown as %s.":                                                                                                       "%s(으)로 표시됩니다.",
+		"You're #%d in the queue.":                                                                                                     "대기열 #%d번입니다.",
+		"You're #%d — #%d on the waitlist.":                                                                                            "#%d번입니다 — 대기 명단 #%d번.",
+		"You're already in the queue at position %d.":                                                                                  "이미 대기열 %d번에 있습니다.",
+		"You're in the queue. Leave it first to spectate.":                                                                             "대기열에 있습니다. 관전하려면 먼저 나가세요.",
+		"You're in the queue. Leave it first to sub.":                                                                                  "대기열에 있습니다. 대타로 대기하려면 먼저 나가세요.",
+		"You're not in the queue.":                                                                                                     "대기열에 없습니다.",
+		"Your roles were cleared.":                                                                                                     "역할을 지웠습니다.",
+		"e.g. ranked only, mic required":                                                                                               "예: 랭크 전용, 마이크 필수",
+		"standby":                                                                                                                      "대기열",
+		"standby-kick":                                                                                                                 "대기열-추방",
+		"thread":                                                                                                                       "스레드",
+		"user":                                                                                                                         "사용자",
+		"✅ The stack filled\n":                                                                                                         "✅ 스택이 찼습니다\n",
+		"❄️ The stack is full, warm up for a game!":                                                                                    "❄️ 스택이 찼습니다, 몸을 녹이고 게임하세요!",
+		"➕ %s joined\n":                                                                                                                "➕ %s 참가\n",
+		"➖ %s left\n":                                                                                                                  "➖ %s 나감\n",
+		"⬆️ %s was promoted\n":                                                                                                         "⬆️ %s 승격\n",
+		"🎃 The stack has risen! Time for a game!":                                                                                      "🎃 스택이 되살아났습니다! 게임할 시간입니다!",
+		"🎉 New stack, new year! Time for a game!":                                                                                      "🎉 새 스택, 새해! 게임할 시간입니다!",
+		"👢 %s was kicked\n":                                                                                                            "👢 %s 추방\n",
+		"🔀 %s shuffled the queue\n":                                                                                                    "🔀 %s 님이 대기열을 섞었습니다\n",
+		"🔒 The queue closed\n":                                                                                                         "🔒 대기열이 닫혔습니다\n",
+	},
+}
+
+// tr translates an English format string into Locale and formats it.
+func tr(format string, args ...any) string {
+	if msg, ok := catalogs[Locale][format]; ok {
+		format = msg
+	}
+	return fmt.Sprintf(format, args...)
+}
+
+// localizations returns every catalog's translation of an English command
+// name or description, for Discord to show in those locales.
+func localizations(en string) *map[discordgo.Locale]string {
+	l := map[discordgo.Locale]string{}
+	for locale, catalog := range catalogs {
+		if msg, ok := catalog[en]; ok {
+			l[discordgo.Locale(locale)] = msg
+		}
+	}
+	return &l
+}
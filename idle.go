@@ -0,0 +1,72 @@
+package main
+
+import (
+	"log/slog"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+var (
+	// IdleTimeout closes a queue that has seen no joins or leaves for this
+	// long. 0 keeps idle queues open.
+	IdleTimeout = durationEnv("STANDBY_IDLE_TIMEOUT", 0)
+	// IdleWarning is how long before an idle close a warning is posted.
+	IdleWarning = durationEnv("STANDBY_IDLE_WARNING", 15*time.Minute)
+)
+
+// touchIdleLocked restarts the idle countdown after queue activity,
+// retracting any idle warning already posted.
+//
+// lock must be held
+func (q *queueState) touchIdleLocked(s *discordgo.Session) {
+	q.stopIdleLocked(s)
+	if IdleTimeout <= 0 {
+		return
+	}
+
+	var warn *time.Timer
+	warn = time.AfterFunc(max(IdleTimeout-IdleWarning, 0), func() {
+		q.Lock()
+		defer q.Unlock()
+
+		if q.idleTimer != warn || q.currentMsgID == "" {
+			return
+		}
+		content := tr("This queue has been idle and will close <t:%d:R> unless someone joins or leaves.",
+			time.Now().Add(IdleWarning).Unix())
+		if m, err := sendMessage(s, q.channelLocked(), &discordgo.MessageSend{Content: content}); err != nil {
+			slog.Error("error sending idle warning", "err", err)
+		} else {
+			q.idleWarnMsgID = m.ID
+		}
+
+		var closeTimer *time.Timer
+		closeTimer = time.AfterFunc(IdleWarning, func() {
+			q.Lock()
+			defer q.Unlock()
+
+			if q.idleTimer != closeTimer || q.currentMsgID == "" {
+				return
+			}
+			slog.Info("closing idle queue", "message", q.currentMsgID)
+			q.closeQueueLocked(s)
+		})
+		q.idleTimer = closeTimer
+	})
+	q.idleTimer = warn
+}
+
+// lock must be held
+func (q *queueState) stopIdleLocked(s *discordgo.Session) {
+	if q.idleTimer != nil {
+		q.idleTimer.Stop()
+		q.idleTimer = nil
+	}
+	if q.idleWarnMsgID != "" {
+		if err := deleteMessage(s, q.channelLocked(), q.idleWarnMsgID); err != nil {
+			slog.Error("error deleting idle warning", "err", err, "message", q.idleWarnMsgID)
+		}
+		q.idleWarnMsgID = ""
+	}
+}
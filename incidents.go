@@ -0,0 +1,153 @@
+package main
+
+import (
+	"context"
+	"log/slog"
+	"net/http"
+	"sync"
+	"time"
+)
+
+const (
+	maxIncidents = 50
+
+	// An error spike is recorded once errorSpikeThreshold errors are logged
+	// within errorSpikeWindow, and ends after a quiet window.
+	errorSpikeThreshold = 10
+	errorSpikeWindow    = 5 * time.Minute
+
+	// degradedQuiet is how long an error spike must go without new errors
+	// before Discord is considered healthy again.
+	degradedQuiet = time.Minute
+)
+
+// incidents tracks gateway outages and error spikes for the status endpoint.
+var incidents = &incidentLog{started: time.Now()}
+
+type incident struct {
+	Kind   string     `json:"kind"`
+	Start  time.Time  `json:"start"`
+	End    *time.Time `json:"end,omitempty"`
+	Errors int        `json:"errors,omitempty"`
+}
+
+type incidentLog struct {
+	sync.Mutex
+
+	started   time.Time
+	incidents []*incident
+
+	gateway *incident
+	spike   *incident
+	errors  []time.Time
+}
+
+// lock must be held
+func (l *incidentLog) addLocked(inc *incident) {
+	l.incidents = append(l.incidents, inc)
+	if len(l.incidents) > maxIncidents {
+		l.incidents = l.incidents[len(l.incidents)-maxIncidents:]
+	}
+}
+
+func (l *incidentLog) gatewayDown() {
+	l.Lock()
+	defer l.Unlock()
+
+	if l.gateway == nil {
+		l.gateway = &incident{Kind: "gateway", Start: time.Now()}
+		l.addLocked(l.gateway)
+	}
+}
+
+func (l *incidentLog) gatewayUp() {
+	l.Lock()
+	defer l.Unlock()
+
+	if l.gateway != nil {
+		now := time.Now()
+		l.gateway.End = &now
+		l.gateway = nil
+	}
+}
+
+func (l *incidentLog) recordError(t time.Time) {
+	l.Lock()
+	defer l.Unlock()
+
+	if l.spike != nil && t.Sub(*l.spike.End) > errorSpikeWindow {
+		l.spike = nil
+	}
+
+	l.errors = append(l.errors, t)
+	for len(l.errors) > 0 && t.Sub(l.errors[0]) > errorSpikeWindow {
+		l.errors = l.errors[1:]
+	}
+
+	switch {
+	case l.spike != nil:
+		l.spike.End = &t
+		l.spike.Errors++
+	case len(l.errors) >= errorSpikeThreshold:
+		l.spike = &incident{Kind: "errors", Start: l.errors[0], End: &t, Errors: len(l.errors)}
+		l.addLocked(l.spike)
+	}
+}
+
+// degraded reports whether an error spike is ongoing, in which case Discord
+// calls that can wait should be held back.
+func (l *incidentLog) degraded() bool {
+	l.Lock()
+	defer l.Unlock()
+
+	return l.spike != nil && time.Since(*l.spike.End) < degradedQuiet
+}
+
+type statusResponse struct {
+	Gateway             string     `json:"gateway"`
+	Started             time.Time  `json:"started"`
+	PendingInteractions int64      `json:"pending_interactions"`
+	ShedInteractions    int64      `json:"shed_interactions"`
+	Incidents           []incident `json:"incidents"`
+}
+
+func (l *incidentLog) handleStatus(w http.ResponseWriter, r *http.Request) {
+	l.Lock()
+	resp := statusResponse{
+		Gateway:             "connected",
+		Started:             l.started,
+		PendingInteractions: pendingInteractions.Load(),
+		ShedInteractions:    shedInteractions.Load(),
+		Incidents:           make([]incident, 0, len(l.incidents)),
+	}
+	if l.gateway != nil {
+		resp.Gateway = "disconnected"
+	}
+	// Newest first.
+	for idx := len(l.incidents) - 1; idx >= 0; idx-- {
+		resp.Incidents = append(resp.Incidents, *l.incidents[idx])
+	}
+	l.Unlock()
+
+	writeJSON(w, http.StatusOK, resp)
+}
+
+// incidentHandler feeds error-level log records into the incident log.
+type incidentHandler struct {
+	slog.Handler
+}
+
+func (h incidentHandler) Handle(ctx context.Context, r slog.Record) error {
+	if r.Level >= slog.LevelError {
+		incidents.recordError(r.Time)
+	}
+	return h.Handler.Handle(ctx, r)
+}
+
+func (h incidentHandler) WithAttrs(attrs []slog.Attr) slog.Handler {
+	return incidentHandler{h.Handler.WithAttrs(attrs)}
+}
+
+func (h incidentHandler) WithGroup(name string) slog.Handler {
+	return incidentHandler{h.Handler.WithGroup(name)}
+}
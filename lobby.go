@@ -0,0 +1,101 @@
+package main
+
+import (
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// lobbyCodeModalID is the custom ID of the modal asking for a lobby code.
+const lobbyCodeModalID = "lobby_code"
+
+// lobbyCodeRow holds the button on the ready notification that lets the
+// organizer share the lobby code privately.
+func lobbyCodeRow() discordgo.MessageComponent {
+	return discordgo.ActionsRow{
+		Components: []discordgo.MessageComponent{
+			discordgo.Button{
+				Label:    tr("Post lobby code"),
+				Style:    discordgo.PrimaryButton,
+				CustomID: "post_lobby_code",
+			},
+		},
+	}
+}
+
+// handlePostLobbyCodeLocked asks the organizer for the lobby code.
+//
+// lock must be held
+func (q *queueState) handlePostLobbyCodeLocked(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if i.Message.ID != q.notifyMsgID {
+		respondEphemeral(s, i, tr("This stack is no longer ready."))
+		return
+	}
+	if !q.canManageLocked(s, i) {
+		respondEphemeral(s, i, tr("Only admins or the queue creator can post the lobby code."))
+		return
+	}
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseModal,
+		Data: &discordgo.InteractionResponseData{
+			CustomID: lobbyCodeModalID,
+			Title:    tr("Post lobby code"),
+			Components: []discordgo.MessageComponent{
+				discordgo.ActionsRow{Components: []discordgo.MessageComponent{
+					discordgo.TextInput{
+						CustomID:  "code",
+						Label:     tr("Lobby code"),
+						Style:     discordgo.TextInputShort,
+						Required:  true,
+						MaxLength: 100,
+					},
+				}},
+			},
+		},
+	})
+}
+
+// handleLobbyCodeSubmit DMs the lobby code to the players in the ready
+// stack, keeping it out of the public channel.
+func (q *queueState) handleLobbyCodeSubmit(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	row := i.ModalSubmitData().Components[0].(*discordgo.ActionsRow)
+	code := strings.TrimSpace(row.Components[0].(*discordgo.TextInput).Value)
+
+	q.Lock()
+	if q.notifyMsgID == "" {
+		q.Unlock()
+		respondEphemeral(s, i, tr("This stack is no longer ready."))
+		return
+	}
+	stack := append([]*discordgo.User(nil), q.lastStack...)
+	content := tr("Lobby code from %s for your %s game: `%s`", q.nameLocked(i.Member.User), q.preset.Name, code)
+	q.Unlock()
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseDeferredChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{Flags: discordgo.MessageFlagsEphemeral},
+	})
+	logger := interactionLogger(i)
+
+	var failed []string
+	for _, user := range stack {
+		if isSimulated(user.ID) {
+			continue
+		}
+		ch, err := s.UserChannelCreate(user.ID)
+		if err == nil {
+			_, err = sendMessage(s, ch.ID, &discordgo.MessageSend{Content: content})
+		}
+		if err != nil {
+			logger.Warn("error sending lobby code", "err", err, "target", user.ID)
+			failed = append(failed, mention(user))
+		}
+	}
+
+	if len(failed) > 0 {
+		followupEphemeral(s, i, tr("Sent the lobby code, but couldn't DM %s. They may have DMs turned off.", strings.Join(failed, ", ")))
+		return
+	}
+	followupEphemeral(s, i, tr("Sent the lobby code to the %d players in the stack.", len(stack)))
+}
@@ -2,12 +2,17 @@ package main
 
 import (
 	"fmt"
-	"log"
+	"log/slog"
+	"math"
 	"net"
+	"net/http"
 	"os"
 	"os/signal"
+	"strconv"
 	"strings"
 	"sync"
+	"sync/atomic"
+	"syscall"
 	"time"
 
 	"github.com/bwmarrin/discordgo"
@@ -19,9 +24,84 @@ var (
 	GuildID     = os.Getenv("STANDBY_GUILD_ID")
 	AdminRoleID = os.Getenv("STANDBY_ADMIN_ID")
 	ChannelID   = os.Getenv("STANDBY_CHANNEL_ID")
+
+	// WatcherRoleID is an optional role held by users while they are queued.
+	WatcherRoleID = os.Getenv("STANDBY_WATCHER_ROLE_ID")
+
+	// ButtonCooldown is the minimum time between a user's Join/Leave clicks.
+	ButtonCooldown = durationEnv("STANDBY_BUTTON_COOLDOWN", 2*time.Second)
+)
+
+// durationEnv parses a duration such as "90s" from the environment, falling
+// back to def when unset.
+func durationEnv(key string, def time.Duration) time.Duration {
+	v := os.Getenv(key)
+	if v == "" {
+		return def
+	}
+	d, err := time.ParseDuration(v)
+	if err != nil {
+		panic(fmt.Sprintf("invalid %s: %v", key, err))
+	}
+	return d
+}
+
+// stringEnv reads a string from the environment, falling back to def when
+// unset.
+func stringEnv(key, def string) string {
+	if v := os.Getenv(key); v != "" {
+		return v
+	}
+	return def
+}
+
+// intEnv parses an integer from the environment, falling back to def when
+// unset.
+func intEnv(key string, def int) int {
+	v := os.Getenv(key)
+	if v == "" {
+		return def
+	}
+	n, err := strconv.Atoi(v)
+	if err != nil {
+		panic(fmt.Sprintf("invalid %s: %v", key, err))
+	}
+	return n
+}
+
+const queueTitle = "5-Stack Standby Queue"
+
+// maxPendingInteractions bounds how many interactions may be waiting on the
+// queue lock at once. Past it, interactions are rejected instead of piling up
+// beyond Discord's 3 second response deadline.
+const maxPendingInteractions = 20
+
+var (
+	pendingInteractions atomic.Int64
+	shedInteractions    atomic.Int64
 )
 
+// editDebounce is how long button clicks are batched before the queue
+// message is edited, so a burst of joins and leaves costs a single edit.
+const editDebounce = 500 * time.Millisecond
+
+// degradedRetry is how often a held back edit is retried while Discord is
+// degraded.
+const degradedRetry = 15 * time.Second
+
+// stackSize is the number of players needed for a game in the default
+// preset. Users queued beyond a preset's size are on the waitlist.
+const stackSize = 5
+
 func main() {
+	var level slog.Level
+	if v := os.Getenv("STANDBY_LOG_LEVEL"); v != "" {
+		if err := level.UnmarshalText([]byte(v)); err != nil {
+			panic(err)
+		}
+	}
+	slog.SetDefault(slog.New(incidentHandler{slog.NewTextHandler(os.Stderr, &slog.HandlerOptions{Level: level})}))
+
 	l, err := net.Listen("tcp4", "0.0.0.0:8080")
 	if err != nil {
 		panic(err)
@@ -32,11 +112,21 @@ func main() {
 	if err != nil {
 		panic(err)
 	}
+	// GuildMembers is privileged and must also be enabled in the developer
+	// portal; it's needed to notice queued users leaving the guild.
+	discord.Identify.Intents = discordgo.IntentsAllWithoutPrivileged | discordgo.IntentsGuildMembers
 	if err := discord.Open(); err != nil {
 		panic(err)
 	}
 	defer discord.Close()
 
+	discord.AddHandler(func(s *discordgo.Session, _ *discordgo.Disconnect) {
+		incidents.gatewayDown()
+	})
+	discord.AddHandler(func(s *discordgo.Session, _ *discordgo.Connect) {
+		incidents.gatewayUp()
+	})
+
 	if err := discord.UpdateStatusComplex(discordgo.UpdateStatusData{
 		Status: "idle",
 		Activities: []*discordgo.Activity{
@@ -52,8 +142,41 @@ func main() {
 
 	{
 		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
-			Name:        "standby",
-			Description: "Open standby queue",
+			Name:                     "standby",
+			NameLocalizations:        localizations("standby"),
+			Description:              "Open standby queue",
+			DescriptionLocalizations: localizations("Open standby queue"),
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:                     discordgo.ApplicationCommandOptionBoolean,
+					Name:                     "thread",
+					NameLocalizations:        *localizations("thread"),
+					Description:              "Open the queue in a new thread",
+					DescriptionLocalizations: *localizations("Open the queue in a new thread"),
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "at",
+					Description: "Open the queue later instead, at a 24-hour time like 20:30",
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionBoolean,
+					Name:        "details",
+					Description: "Add a title, game and notes to the queue",
+				},
+				{
+					Type:         discordgo.ApplicationCommandOptionString,
+					Name:         "preset",
+					Description:  "Game mode to open the queue with",
+					Autocomplete: true,
+				},
+				{
+					Type:         discordgo.ApplicationCommandOptionString,
+					Name:         "game",
+					Description:  "Game the queue is for",
+					Autocomplete: true,
+				},
+			},
 		})
 		if err != nil {
 			panic(err)
@@ -63,340 +186,1932 @@ func main() {
 	{
 		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
 			Name:        "standby-close",
-			Description: "Admin command to close existing standby",
+			Description: "Close the existing standby queue or cancel a scheduled one",
 		})
 		if err != nil {
 			panic(err)
 		}
 		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
 	}
-
-	q := queueState{}
-
-	remove := discord.AddHandler(func(s *discordgo.Session, i *discordgo.InteractionCreate) {
-		switch i.Type {
-		case discordgo.InteractionApplicationCommand:
-			q.handleSlashCommand(s, i)
-		case discordgo.InteractionMessageComponent:
-			q.handleButtonClick(s, i)
-		}
-	})
-	defer remove()
-
-	stop := make(chan os.Signal, 1)
-	signal.Notify(stop, os.Interrupt)
-	log.Println("Press ctrl+c to exit")
-	<-stop
-
-	log.Println("exiting")
-}
-
-type queueState struct {
-	sync.Mutex
-
-	currentMsgID string
-	notifyMsgID  string
-
-	lastUser   *discordgo.User
-	lastAction string
-
-	users []*discordgo.User
-
-	startTime time.Time
-}
-
-// lock must be held
-func (q *queueState) buildStringLocked() string {
-	var sb strings.Builder
-	switch q.lastAction {
-	case "join":
-		sb.WriteString(fmt.Sprintf("%s joined queue!\n", q.lastUser.Username))
-	case "leave":
-		sb.WriteString(fmt.Sprintf("%s left queue!\n", q.lastUser.Username))
-	}
-	sb.WriteString(fmt.Sprintf("### Queued users (%d):\n", len(q.users)))
-	for _, user := range q.users {
-		sb.WriteString(fmt.Sprintf("<@%s>\n", user.ID))
-	}
-
-	return sb.String()
-}
-
-func (q *queueState) handleSlashCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
-	switch i.ApplicationCommandData().Name {
-	case "standby":
-		q.Lock()
-		defer q.Unlock()
-
-		if q.currentMsgID != "" {
-			s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
-				Type: discordgo.InteractionResponseChannelMessageWithSource,
-				Data: &discordgo.InteractionResponseData{
-					Content: "There is already an existing queue.",
-					Flags:   discordgo.MessageFlagsEphemeral,
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:                     "standby-kick",
+			NameLocalizations:        localizations("standby-kick"),
+			Description:              "Remove a user from the queue",
+			DescriptionLocalizations: localizations("Remove a user from the queue"),
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:                     discordgo.ApplicationCommandOptionUser,
+					Name:                     "user",
+					NameLocalizations:        *localizations("user"),
+					Description:              "User to remove",
+					DescriptionLocalizations: *localizations("User to remove"),
+					Required:                 true,
 				},
-			})
-			return
-		}
-
-		if err := q.openQueueLocked(s); err != nil {
-			log.Printf("error opening queue: %v", err)
-			return
-		}
-
-		s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
-			Type: discordgo.InteractionResponseChannelMessageWithSource,
-			Data: &discordgo.InteractionResponseData{
-				Content: "Starting queue.",
-				Flags:   discordgo.MessageFlagsEphemeral,
 			},
 		})
-
-	case "standby-close":
-		userID := i.Member.User.ID
-		m, err := s.GuildMember(GuildID, userID)
 		if err != nil {
-			log.Printf("error fetching member: %v\n", err)
-		}
-		var isAdmin bool
-		for _, r := range m.Roles {
-			if r == AdminRoleID {
-				isAdmin = true
-				break
-			}
+			panic(err)
 		}
-		if !isAdmin {
-			s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
-				Type: discordgo.InteractionResponseChannelMessageWithSource,
-				Data: &discordgo.InteractionResponseData{
-					Content: "Only admins can use this command.",
-					Flags:   discordgo.MessageFlagsEphemeral,
-				},
-			})
-		} else {
-			q.Lock()
-			defer q.Unlock()
-
-			if q.currentMsgID == "" {
-				s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
-					Type: discordgo.InteractionResponseChannelMessageWithSource,
-					Data: &discordgo.InteractionResponseData{
-						Content: "No active queue to close.",
-						Flags:   discordgo.MessageFlagsEphemeral,
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "result",
+			Description: "Record the result of the last full stack",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "outcome",
+					Description: "Did the stack win or lose?",
+					Required:    true,
+					Choices: []*discordgo.ApplicationCommandOptionChoice{
+						{Name: "Win", Value: "win"},
+						{Name: "Loss", Value: "loss"},
 					},
-				})
-			}
-			q.closeQueueLocked(s)
-
-			s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
-				Type: discordgo.InteractionResponseChannelMessageWithSource,
-				Data: &discordgo.InteractionResponseData{
-					Content: "Closing queue.",
-					Flags:   discordgo.MessageFlagsEphemeral,
 				},
-			})
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "score",
+					Description: "Final score, e.g. 13-9",
+					MaxLength:   32,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
 		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
 	}
-}
-
-// lock must be held
-func (q *queueState) openQueueLocked(s *discordgo.Session) error {
-	q.startTime = time.Now()
-	msg, err := s.ChannelMessageSendComplex(ChannelID, &discordgo.MessageSend{
-		Embeds: []*discordgo.MessageEmbed{
-			{
-				Type:        discordgo.EmbedTypeRich,
-				Title:       "5-Stack Standby Queue",
-				Color:       0x0099FF,
-				Description: q.buildStringLocked(),
-			},
-		},
-		Components: []discordgo.MessageComponent{
-			discordgo.ActionsRow{
-				Components: []discordgo.MessageComponent{
-					discordgo.Button{
-						Label:    "Join",
-						Style:    discordgo.PrimaryButton,
-						CustomID: "join_queue",
-					},
-					discordgo.Button{
-						Label:    "Leave",
-						Style:    discordgo.DangerButton,
-						CustomID: "leave_queue",
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "stats",
+			Description: "Show rating, record and queue stats",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "user",
+					Description: "Show a user's rating, record and queue stats",
+					Options: []*discordgo.ApplicationCommandOption{
+						{
+							Type:        discordgo.ApplicationCommandOptionUser,
+							Name:        "user",
+							Description: "User to show stats for (defaults to you)",
+						},
 					},
-					discordgo.Button{
-						Label:    "Close",
-						Style:    discordgo.SecondaryButton,
-						CustomID: "close_queue",
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "server",
+					Description: "Show how quickly queues fill and when it's easiest to get a game",
+					Options: []*discordgo.ApplicationCommandOption{
+						{
+							Type:         discordgo.ApplicationCommandOptionString,
+							Name:         "game",
+							Description:  "Only count queues for this game",
+							Autocomplete: true,
+						},
 					},
 				},
 			},
-		},
-	})
-	if err != nil {
-		return err
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
 	}
-	q.currentMsgID = msg.ID
-	return nil
-}
-
-// lock must be held
-func (q *queueState) closeQueueLocked(s *discordgo.Session) {
-	_, err := s.ChannelMessageEditComplex(&discordgo.MessageEdit{
-		ID:      q.currentMsgID,
-		Channel: ChannelID,
-		Embeds: []*discordgo.MessageEmbed{
-			{
-				Type:        discordgo.EmbedTypeRich,
-				Title:       "5-Stack Standby Queue",
-				Color:       0x0099FF,
-				Description: "Queue is closed",
-			},
-		},
-		Components: []discordgo.MessageComponent{
-			discordgo.ActionsRow{
-				Components: []discordgo.MessageComponent{
-					discordgo.Button{
-						Label:    "Join",
-						Style:    discordgo.PrimaryButton,
-						CustomID: "join_queue",
-						Disabled: true,
-					},
-					discordgo.Button{
-						Label:    "Leave",
-						Style:    discordgo.DangerButton,
-						CustomID: "leave_queue",
-						Disabled: true,
-					},
-					discordgo.Button{
-						Label:    "Open",
-						Style:    discordgo.SecondaryButton,
-						CustomID: "open_queue",
-					},
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "feedback",
+			Description: "Send a suggestion or bug report to the bot maintainer",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "text",
+					Description: "Your feedback",
+					Required:    true,
+					MaxLength:   1000,
 				},
 			},
-		},
-	})
-	if err != nil {
-		log.Printf("error editing message closing queue: %v", err)
-	}
-
-	q.currentMsgID = ""
-	q.lastAction = ""
-	q.lastUser = nil
-	q.users = nil
-}
-
-func (q *queueState) handleButtonClick(s *discordgo.Session, i *discordgo.InteractionCreate) {
-	q.Lock()
-	defer q.Unlock()
-
-	switch i.MessageComponentData().CustomID {
-	case "close_queue":
-		q.closeQueueLocked(s)
-		s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
-			Type: discordgo.InteractionResponseUpdateMessage,
 		})
-		return
-	case "open_queue":
-		// Add the user who opened queue
-		q.users = append(q.users, i.Member.User)
-		q.lastUser = i.Member.User
-		q.lastAction = "join"
-
-		q.openQueueLocked(s)
-
-		// Delete the original message to clean up clutter
-		if err := s.ChannelMessageDelete(ChannelID, i.Message.ID); err != nil {
-			log.Printf("error deleting active message: %v\n", err)
+		if err != nil {
+			panic(err)
 		}
-
-		s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
-			Type: discordgo.InteractionResponseUpdateMessage,
-		})
-		return
-	case "join_queue":
-		for _, user := range q.users {
-			if user.ID == i.Member.User.ID {
-				return
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		party := make([]*discordgo.ApplicationCommandOption, maxPartySize-1)
+		for idx := range party {
+			party[idx] = &discordgo.ApplicationCommandOption{
+				Type:        discordgo.ApplicationCommandOptionUser,
+				Name:        fmt.Sprintf("friend%d", idx+1),
+				Description: "Friend to queue with",
+				Required:    idx == 0,
 			}
 		}
-		q.users = append(q.users, i.Member.User)
-		q.lastUser = i.Member.User
-		q.lastAction = "join"
-	case "leave_queue":
-		for idx, user := range q.users {
-			if user.ID == i.Member.User.ID {
-				q.users = append(q.users[:idx], q.users[idx+1:]...)
-			}
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "join",
+			Description: "Join the queue together with friends",
+			Options:     party,
+		})
+		if err != nil {
+			panic(err)
 		}
-		q.lastUser = i.Member.User
-		q.lastAction = "leave"
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
 	}
-	_, err := s.ChannelMessageEditComplex(&discordgo.MessageEdit{
-		ID:      q.currentMsgID,
-		Channel: ChannelID,
-		Embeds: []*discordgo.MessageEmbed{
-			{
-				Type:        discordgo.EmbedTypeRich,
-				Title:       "5-Stack Standby Queue",
-				Color:       0x0099FF,
-				Description: q.buildStringLocked(),
-			},
-		},
-		Components: []discordgo.MessageComponent{
-			discordgo.ActionsRow{
-				Components: []discordgo.MessageComponent{
-					discordgo.Button{
-						Label:    "Join",
-						Style:    discordgo.PrimaryButton,
-						CustomID: "join_queue",
-					},
-					discordgo.Button{
-						Label:    "Leave",
-						Style:    discordgo.DangerButton,
-						CustomID: "leave_queue",
-					},
-					discordgo.Button{
-						Label:    "Close",
-						Style:    discordgo.SecondaryButton,
-						CustomID: "close_queue",
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "notify",
+			Description: "Control whether you are pinged when the stack is ready",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "mute",
+					Description: "Stop ready pings for a while; you still show in the queue",
+					Options: []*discordgo.ApplicationCommandOption{
+						{
+							Type:        discordgo.ApplicationCommandOptionString,
+							Name:        "duration",
+							Description: "How long to mute for, e.g. 30m or 2h",
+							Required:    true,
+						},
 					},
 				},
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "unmute",
+					Description: "Get ready pings again",
+				},
 			},
-		},
-	})
-	if err != nil {
-		log.Printf("error editing message handling button click: %v", err)
-		return
-	}
-	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
-		Type: discordgo.InteractionResponseUpdateMessage,
-	})
-
-	// Close queue is a user leaving would leave it at 0
-	if len(q.users) == 0 {
-		q.closeQueueLocked(s)
-	}
-
-	if len(q.users) >= 5 && q.notifyMsgID == "" {
-		usernames := make([]string, len(q.users))
-		for i, user := range q.users {
-			usernames[i] = fmt.Sprintf("<@%s>", user.ID)
-		}
-
-		m, err := s.ChannelMessageSend(ChannelID, fmt.Sprintf("There are enough users for a game! %s", strings.Join(usernames, ", ")))
+		})
 		if err != nil {
-			log.Printf("error sending channel message: %v\n", err)
-			return
+			panic(err)
 		}
-		q.notifyMsgID = m.ID
-	} else {
-		if q.notifyMsgID != "" {
-			if err := s.ChannelMessageDelete(ChannelID, q.notifyMsgID); err != nil {
-				log.Printf("error deleting active message: %v\n", err)
-			}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-export",
+			Description: "Admin command to export the queue, settings and history as JSON",
+		})
+		if err != nil {
+			panic(err)
 		}
-		q.notifyMsgID = ""
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
 	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-backup",
+			Description: "Admin command to download aliases, ratings, results and stats",
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-restore",
+			Description: "Admin command to load a file made with /standby-backup",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionAttachment,
+					Name:        "file",
+					Description: "Backup file",
+					Required:    true,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		minCount := 1.0
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "history",
+			Description: "Show recent queue events",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionInteger,
+					Name:        "count",
+					Description: "Number of events to show (default 10)",
+					MinValue:    &minCount,
+					MaxValue:    25,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-swap",
+			Description: "Swap the queue positions of two users (admins or queue creator)",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionUser,
+					Name:        "first",
+					Description: "First user",
+					Required:    true,
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionUser,
+					Name:        "second",
+					Description: "Second user",
+					Required:    true,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-shuffle",
+			Description: "Randomize the queue order (admins or queue creator)",
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-promote",
+			Description: "Admin command to move a waitlisted user into the stack",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionUser,
+					Name:        "user",
+					Description: "User to promote",
+					Required:    true,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		minSize, minTeamSize, minAlert := 2.0, 1.0, 1.0
+		presetName := &discordgo.ApplicationCommandOption{
+			Type:         discordgo.ApplicationCommandOptionString,
+			Name:         "name",
+			Description:  "Preset name",
+			Required:     true,
+			MaxLength:    32,
+			Autocomplete: true,
+		}
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-preset",
+			Description: "Admin command to manage game-mode presets",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "set",
+					Description: "Create or replace a preset",
+					Options: []*discordgo.ApplicationCommandOption{
+						presetName,
+						{
+							Type:        discordgo.ApplicationCommandOptionInteger,
+							Name:        "size",
+							Description: "Players needed for a game",
+							Required:    true,
+							MinValue:    &minSize,
+							MaxValue:    25,
+						},
+						{
+							Type:        discordgo.ApplicationCommandOptionInteger,
+							Name:        "team_size",
+							Description: "Players per team for Split Teams and Draft (defaults to size)",
+							MinValue:    &minTeamSize,
+							MaxValue:    12,
+						},
+						{
+							Type:        discordgo.ApplicationCommandOptionString,
+							Name:        "team_split",
+							Description: "How Split Teams builds teams",
+							Choices: []*discordgo.ApplicationCommandOptionChoice{
+								{Name: "Random", Value: "random"},
+								{Name: "Balanced by rating", Value: "rating"},
+							},
+						},
+						{
+							Type:        discordgo.ApplicationCommandOptionInteger,
+							Name:        "alert_after",
+							Description: "Minutes one player short before the alert role is pinged",
+							MinValue:    &minAlert,
+							MaxValue:    24 * 60,
+						},
+						{
+							Type:        discordgo.ApplicationCommandOptionString,
+							Name:        "color",
+							Description: "Queue embed color, e.g. #FF7518",
+							MaxLength:   7,
+						},
+					},
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "delete",
+					Description: "Delete a preset",
+					Options:     []*discordgo.ApplicationCommandOption{presetName},
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "list",
+					Description: "List the presets",
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		minSize := 2.0
+		gameName := &discordgo.ApplicationCommandOption{
+			Type:         discordgo.ApplicationCommandOptionString,
+			Name:         "name",
+			Description:  "Game name",
+			Required:     true,
+			MaxLength:    50,
+			Autocomplete: true,
+		}
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-game",
+			Description: "Admin command to manage the games queues can be opened for",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "set",
+					Description: "Register a game or replace its settings",
+					Options: []*discordgo.ApplicationCommandOption{
+						{
+							Type:        discordgo.ApplicationCommandOptionString,
+							Name:        "name",
+							Description: "Game name",
+							Required:    true,
+							MaxLength:   50,
+						},
+						{
+							Type:        discordgo.ApplicationCommandOptionString,
+							Name:        "icon",
+							Description: "Image URL shown on the queue",
+						},
+						{
+							Type:        discordgo.ApplicationCommandOptionInteger,
+							Name:        "size",
+							Description: "Players needed for a game, unless /standby picks a preset",
+							MinValue:    &minSize,
+							MaxValue:    25,
+						},
+						{
+							Type:        discordgo.ApplicationCommandOptionString,
+							Name:        "color",
+							Description: "Queue embed color, e.g. #FF4655",
+							MaxLength:   7,
+						},
+					},
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "delete",
+					Description: "Remove a game",
+					Options:     []*discordgo.ApplicationCommandOption{gameName},
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "list",
+					Description: "List the games",
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Type: discordgo.UserApplicationCommand,
+			Name: addToQueueCommand,
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		minUsers := 1.0
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-simulate",
+			Description: "Admin command to fill the queue with simulated users",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionInteger,
+					Name:        "users",
+					Description: "Number of simulated users to add",
+					Required:    true,
+					MinValue:    &minUsers,
+					MaxValue:    20,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		minLead := 0.0
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-event",
+			Description: "Admin command to open the queue ahead of a scheduled event",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:         discordgo.ApplicationCommandOptionString,
+					Name:         "event",
+					Description:  "Scheduled event to link",
+					Required:     true,
+					Autocomplete: true,
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionInteger,
+					Name:        "lead",
+					Description: "Minutes before the event to open the queue (default 15)",
+					MinValue:    &minLead,
+					MaxValue:    240,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-announce",
+			Description: "Admin command to show a notice on the next queue message",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "text",
+					Description: "Notice text, e.g. \"New: /alias lets you pick your queue name\"",
+					Required:    true,
+					MaxLength:   200,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "standby-template",
+			Description: "Admin command to customize notification text",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "kind",
+					Description: "Notification to customize",
+					Required:    true,
+					Choices: []*discordgo.ApplicationCommandOptionChoice{
+						{Name: "Ready", Value: templateReady},
+						{Name: "One more needed", Value: templateOneMore},
+					},
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "text",
+					Description: "Text with {mentions}, {count_needed} or {queue_name}; leave empty to reset",
+					MaxLength:   500,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "alias",
+			Description: "Set the name shown for you in the standby queue",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "set",
+					Description: "Show this name instead of your mention",
+					Options: []*discordgo.ApplicationCommandOption{
+						{
+							Type:        discordgo.ApplicationCommandOptionString,
+							Name:        "name",
+							Description: "Name to display, e.g. your in-game name",
+							Required:    true,
+							MaxLength:   32,
+						},
+					},
+				},
+				{
+					Type:        discordgo.ApplicationCommandOptionSubCommand,
+					Name:        "clear",
+					Description: "Go back to showing your mention",
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "link",
+			Description: "Link your Riot account to show your rank in the queue",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "riot_id",
+					Description: "Your Riot ID, like Name#TAG; leave empty to unlink",
+					MaxLength:   32,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+	{
+		cmd, err := discord.ApplicationCommandCreate(AppID, GuildID, &discordgo.ApplicationCommand{
+			Name:        "setign",
+			Description: "Register your in-game ID for lobby invites",
+			Options: []*discordgo.ApplicationCommandOption{
+				{
+					Type:        discordgo.ApplicationCommandOptionString,
+					Name:        "id",
+					Description: "Your in-game ID; leave empty to remove it",
+					MaxLength:   64,
+				},
+			},
+		})
+		if err != nil {
+			panic(err)
+		}
+		defer discord.ApplicationCommandDelete(AppID, GuildID, cmd.ID)
+	}
+
+	q := newQueueState()
+
+	go q.sweep()
+
+	api := &http.Server{Handler: q.apiHandler(discord)}
+	go func() {
+		if err := api.Serve(l); err != nil && err != http.ErrServerClosed {
+			slog.Error("error serving http", "err", err)
+		}
+	}()
+	defer api.Close()
+
+	q.Lock()
+	q.restoreQueueLocked(discord)
+	q.Unlock()
+
+	remove := discord.AddHandler(func(s *discordgo.Session, i *discordgo.InteractionCreate) {
+		if n := pendingInteractions.Add(1); n > maxPendingInteractions {
+			pendingInteractions.Add(-1)
+			shedInteractions.Add(1)
+			slog.Warn("shedding interaction", "interaction", i.ID, "pending", n)
+			respondEphemeral(s, i, tr("The bot is busy, please try again in a moment."))
+			return
+		}
+		defer pendingInteractions.Add(-1)
+
+		switch i.Type {
+		case discordgo.InteractionApplicationCommand:
+			q.handleSlashCommand(s, i)
+		case discordgo.InteractionApplicationCommandAutocomplete:
+			data := i.ApplicationCommandData()
+			switch {
+			case data.Name == "standby-event":
+				handleEventAutocomplete(s, i)
+			case data.Name == "standby-game" || focusedOption(data.Options).Name == "game":
+				q.handleGameAutocomplete(s, i)
+			default:
+				q.handlePresetAutocomplete(s, i)
+			}
+		case discordgo.InteractionMessageComponent:
+			q.handleButtonClick(s, i)
+		case discordgo.InteractionModalSubmit:
+			if i.ModalSubmitData().CustomID == lobbyCodeModalID {
+				q.handleLobbyCodeSubmit(s, i)
+			} else {
+				q.handleDetailsSubmit(s, i)
+			}
+		}
+	})
+	defer remove()
+
+	removeDelete := discord.AddHandler(q.handleMessageDelete)
+	defer removeDelete()
+	removeCreate := discord.AddHandler(q.handleMessageCreate)
+	defer removeCreate()
+
+	// Reconnects either resume the session or start a new one; resync on
+	// both.
+	removeResumed := discord.AddHandler(func(s *discordgo.Session, _ *discordgo.Resumed) {
+		q.resync(s)
+	})
+	defer removeResumed()
+	removeReady := discord.AddHandler(func(s *discordgo.Session, _ *discordgo.Ready) {
+		q.resync(s)
+	})
+	defer removeReady()
+
+	removeMember := discord.AddHandler(q.handleMemberRemove)
+	defer removeMember()
+
+	removeEventUpdate := discord.AddHandler(q.handleEventUpdate)
+	defer removeEventUpdate()
+	removeEventDelete := discord.AddHandler(q.handleEventDelete)
+	defer removeEventDelete()
+
+	stop := make(chan os.Signal, 1)
+	signal.Notify(stop, os.Interrupt, syscall.SIGTERM)
+	serving.Store(true)
+	slog.Info("Press ctrl+c to exit")
+	<-stop
+
+	slog.Info("exiting")
+	serving.Store(false)
+
+	// Keep holding the lock so no interaction can change the queue after it
+	// has been paused.
+	q.Lock()
+	q.pauseLocked(discord)
+}
+
+type queueState struct {
+	sync.Mutex
+
+	currentMsgID string
+	notifyMsgID  string
+	// closedMsgID is the closed queue message that the next queue reopens.
+	closedMsgID string
+	// compositionMsgID is the message listing roles a full stack still
+	// needs, and compositionMissing what it lists and whom it pings.
+	compositionMsgID   string
+	compositionMissing string
+	// announceMsgIDs are the messages posted to AnnounceChannelID for the
+	// current queue.
+	announceMsgIDs []string
+
+	// creatorID is the user who opened the current queue.
+	creatorID string
+	// preset is how the current or next queue plays, picked from presets
+	// with /standby.
+	preset  preset
+	presets map[string]preset
+	// game is the key in knownGames of the game the current queue is for,
+	// if any.
+	game       string
+	knownGames map[string]game
+	// threadID is the thread the current queue was opened in, if any.
+	threadID string
+	// scheduledOpen is armed while a /standby at: queue is waiting to open
+	// at scheduledAt on behalf of scheduledBy.
+	scheduledOpen *time.Timer
+	scheduledAt   time.Time
+	scheduledBy   string
+	// frozen blocks joins and leaves while keeping the roster, see
+	// handleFreezeLocked.
+	frozen bool
+
+	// feed holds the most recent queue actions, see recordActionLocked;
+	// history holds more of them, along with fills and closes, for /history.
+	feed    []queueAction
+	history []queueAction
+
+	users []*discordgo.User
+	// spectators are watching the current queue without taking a slot.
+	spectators []*discordgo.User
+	// subs are standing by to substitute; subCalls counts the stack slots
+	// they've been pinged to fill, see callSubsLocked.
+	subs     []*discordgo.User
+	subCalls int
+	// offers are the stack slots offered to promoted waitlisters, keyed by
+	// user ID, see offerPromotionLocked.
+	offers map[string]*promotionOffer
+	// joinedAt records when each queued user joined.
+	joinedAt map[string]time.Time
+	// lastWaitlist is the waitlist of the last closed queue, offered back
+	// when it is reopened.
+	lastWaitlist []*discordgo.User
+
+	// aliases maps user IDs to the name shown for them in the queue embed.
+	aliases map[string]string
+	// igns maps user IDs to their registered in-game IDs.
+	igns map[string]string
+	// gameRoles maps user IDs to the GameRoles they picked.
+	gameRoles map[string][]string
+	// riotLinks maps user IDs to the Riot accounts they linked with /link.
+	riotLinks map[string]riotLink
+
+	// pendingNotice is an admin announcement waiting for the next queue
+	// message to be rendered; notice is the one shown on the current message.
+	pendingNotice string
+	notice        string
+
+	// details are the title, game and notes given when opening the queue.
+	details queueDetails
+
+	// eventLinks holds scheduled events the queue opens for, keyed by event
+	// ID; eventID is the event that opened the current queue, if any.
+	eventLinks map[string]*eventLink
+	eventID    string
+
+	// refreshTimer is armed while a coalesced queue message edit is pending.
+	refreshTimer *time.Timer
+	// alertTimer is armed while the queue is stalled one player short, see
+	// updateAlertLocked.
+	alertTimer *time.Timer
+	// chatCount and chatStart track chat below the queue message, see
+	// handleMessageCreate.
+	chatCount int
+	chatStart time.Time
+	// idleTimer counts down to the next idle warning or close, see
+	// touchIdleLocked; idleWarnMsgID is the posted warning, if any.
+	idleTimer     *time.Timer
+	idleWarnMsgID string
+	// reserveTimer is armed until the current queue's reserved slots are
+	// released, see armReservationLocked.
+	reserveTimer *time.Timer
+	// regulars records queued users holding ReservedRoleID.
+	regulars map[string]bool
+
+	// lastStack is the most recent ready stack, awaiting /result; results
+	// are the recorded outcomes.
+	lastStack []*discordgo.User
+	results   []matchResult
+	// ratings holds each user's Elo rating, see applyRatingsLocked.
+	ratings map[string]float64
+	// stats holds each user's queue counters for /stats.
+	stats map[string]*userStats
+
+	// draft is the captains draft in progress, if any.
+	draft *draft
+	// veto is the map veto attached to the ready notification, if any.
+	veto *mapVeto
+
+	// hooks are notified of queue events.
+	hooks []QueueHook
+
+	// lastClick records each user's last accepted Join/Leave click.
+	lastClick map[string]time.Time
+	// templates holds custom notification text by kind, see
+	// renderTemplate.
+	templates map[string]string
+	// lastFeedback records when each user last sent /feedback.
+	lastFeedback map[string]time.Time
+	// seenTips records users who have been sent the onboarding tips.
+	seenTips map[string]bool
+	// mutedUntil records users who muted ready pings, see /notify.
+	mutedUntil map[string]time.Time
+
+	// games records when each user was part of a ready stack.
+	games map[string][]time.Time
+
+	// fills records how long recent queues took to fill, and fillHist all
+	// fill times since startup. fillRecorded is set once the current queue
+	// has been recorded.
+	fills        []queueFill
+	fillHist     fillHistogram
+	fillRecorded bool
+
+	// nextSimulated numbers the fake users added by /standby-simulate.
+	nextSimulated int
+
+	startTime time.Time
+}
+
+// lock must be held
+func (q *queueState) nameLocked(user *discordgo.User) string {
+	if alias, ok := q.aliases[user.ID]; ok {
+		return alias
+	}
+	return user.Username
+}
+
+// lock must be held
+func (q *queueState) mentionLocked(user *discordgo.User) string {
+	if alias, ok := q.aliases[user.ID]; ok {
+		return alias
+	}
+	return mention(user)
+}
+
+// lobbyListLocked lists the in-game IDs of players in the stack who
+// registered one so the party leader can send invites. Returns "" if nobody
+// has.
+//
+// lock must be held
+func (q *queueState) lobbyListLocked() string {
+	var sb strings.Builder
+	for _, user := range q.users[:min(len(q.users), q.preset.Size)] {
+		if ign, ok := q.igns[user.ID]; ok {
+			sb.WriteString(fmt.Sprintf("\n%s → `%s`", q.nameLocked(user), ign))
+		}
+	}
+	if sb.Len() == 0 {
+		return ""
+	}
+	return "\n### Lobby:" + sb.String()
+}
+
+// lock must be held
+func (q *queueState) buildStringLocked() string {
+	var sb strings.Builder
+	sb.WriteString(q.detailsTextLocked())
+	sb.WriteString(q.feedTextLocked())
+	sb.WriteString(q.reservationTextLocked())
+	sb.WriteString(tr("Queue opened <t:%d:R>\n", q.startTime.Unix()))
+	sb.WriteString(tr("### Queued users (%d):\n", len(q.users)))
+	for _, user := range q.users {
+		sb.WriteString(q.mentionLocked(user))
+		if ShowRatings {
+			sb.WriteString(fmt.Sprintf(" (%.0f)", q.ratingLocked(user.ID)))
+		}
+		sb.WriteString(q.gameRolesTextLocked(user.ID))
+		sb.WriteString(q.rankTextLocked(user.ID))
+		if _, ok := q.offers[user.ID]; ok {
+			sb.WriteString(tr(" — waiting to accept"))
+		}
+		if joined, ok := q.joinedAt[user.ID]; ok {
+			sb.WriteString(tr(" — joined <t:%d:R>", joined.Unix()))
+		}
+		sb.WriteString("\n")
+	}
+	sb.WriteString(q.subsTextLocked())
+	sb.WriteString(q.spectatorsTextLocked())
+
+	return sb.String()
+}
+
+// newQueueState returns an empty queue with the built-in presets and games.
+func newQueueState() *queueState {
+	return &queueState{
+		aliases:      map[string]string{},
+		igns:         map[string]string{},
+		presets:      builtinPresets(),
+		knownGames:   builtinGames(),
+		preset:       builtinPresets()[defaultPreset],
+		gameRoles:    map[string][]string{},
+		riotLinks:    map[string]riotLink{},
+		eventLinks:   map[string]*eventLink{},
+		offers:       map[string]*promotionOffer{},
+		games:        map[string][]time.Time{},
+		lastClick:    map[string]time.Time{},
+		lastFeedback: map[string]time.Time{},
+		ratings:      map[string]float64{},
+		seenTips:     map[string]bool{},
+		templates:    map[string]string{},
+		regulars:     map[string]bool{},
+		joinedAt:     map[string]time.Time{},
+		stats:        map[string]*userStats{},
+		mutedUntil:   map[string]time.Time{},
+		hooks:        enabledHooks(),
+	}
+}
+
+// focusedOption returns the option being autocompleted, looking inside
+// subcommands.
+func focusedOption(opts []*discordgo.ApplicationCommandInteractionDataOption) *discordgo.ApplicationCommandInteractionDataOption {
+	for _, opt := range opts {
+		if opt.Focused {
+			return opt
+		}
+		if sub := focusedOption(opt.Options); sub != nil {
+			return sub
+		}
+	}
+	return nil
+}
+
+// interactionLogger returns a logger annotated with where an interaction
+// came from and who triggered it.
+func interactionLogger(i *discordgo.InteractionCreate) *slog.Logger {
+	return slog.With(
+		"interaction", i.ID,
+		"guild", i.GuildID,
+		"channel", i.ChannelID,
+		"user", i.Member.User.ID,
+	)
+}
+
+// isAdmin reports whether the user who triggered the interaction has the
+// configured admin role.
+func isAdmin(s *discordgo.Session, i *discordgo.InteractionCreate) bool {
+	return hasRole(s, i.Member.User.ID, AdminRoleID)
+}
+
+func respondEphemeral(s *discordgo.Session, i *discordgo.InteractionCreate, content string) {
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{
+			Content: content,
+			Flags:   discordgo.MessageFlagsEphemeral,
+		},
+	})
+}
+
+// followupEphemeral sends an ephemeral message after the interaction has
+// already been responded to.
+func followupEphemeral(s *discordgo.Session, i *discordgo.InteractionCreate, content string) {
+	if _, err := s.FollowupMessageCreate(i.Interaction, false, &discordgo.WebhookParams{
+		Content: content,
+		Flags:   discordgo.MessageFlagsEphemeral,
+	}); err != nil {
+		slog.Error("error sending followup message", "err", err, "interaction", i.ID)
+	}
+}
+
+// titleLocked is the queue embed's title: the game's, if the queue is for
+// one, or queueTitle.
+//
+// lock must be held
+func (q *queueState) titleLocked() string {
+	if g, ok := q.knownGames[q.game]; ok {
+		return g.title()
+	}
+	return queueTitle
+}
+
+// queueNameLocked names the current queue as its embed shows it: by the
+// title given in its details or else the embed title, followed by the
+// preset unless it is the default.
+//
+// lock must be held
+func (q *queueState) queueNameLocked() string {
+	name := q.titleLocked()
+	if q.details.Title != "" {
+		name = q.details.Title
+	}
+	if q.preset.Name != defaultPreset {
+		name += " (" + q.preset.Name + ")"
+	}
+	return name
+}
+
+// lock must be held
+func (q *queueState) queueEmbedLocked() *discordgo.MessageEmbed {
+	theme := currentTheme(time.Now())
+	embed := &discordgo.MessageEmbed{
+		Type:        discordgo.EmbedTypeRich,
+		Title:       q.titleLocked(),
+		Color:       theme.Color,
+		Description: q.buildStringLocked(),
+	}
+	if g, ok := q.knownGames[q.game]; ok {
+		embed.Color = g.Color
+		if g.Thumbnail != "" {
+			theme.Thumbnail = g.Thumbnail
+		}
+	}
+	if theme.Thumbnail != "" {
+		embed.Thumbnail = &discordgo.MessageEmbedThumbnail{URL: theme.Thumbnail}
+	}
+	if q.preset.Color != 0 {
+		embed.Color = q.preset.Color
+	}
+	if q.preset.Name != defaultPreset {
+		embed.Author = &discordgo.MessageEmbedAuthor{Name: q.preset.Name}
+	}
+	if q.frozen {
+		embed.Color = frozenColor
+		embed.Description = frozenText + embed.Description
+	}
+	if q.notice != "" {
+		embed.Footer = &discordgo.MessageEmbedFooter{Text: q.notice}
+	}
+	return embed
+}
+
+func (q *queueState) handleSlashCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	logger := interactionLogger(i)
+	logger.Debug("handling command", "command", i.ApplicationCommandData().Name)
+
+	if wait, ok := commandLimiter.take(i.Member.User.ID + ":" + i.ApplicationCommandData().Name); !ok {
+		respondEphemeral(s, i, tr("Slow down! Try again in %ds.", int(math.Ceil(wait.Seconds()))))
+		return
+	}
+
+	switch i.ApplicationCommandData().Name {
+	case "standby":
+		q.Lock()
+		defer q.Unlock()
+
+		if q.currentMsgID != "" {
+			s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+				Type: discordgo.InteractionResponseChannelMessageWithSource,
+				Data: &discordgo.InteractionResponseData{
+					Content: tr("There is already an existing queue."),
+					Flags:   discordgo.MessageFlagsEphemeral,
+				},
+			})
+			return
+		}
+
+		var thread, details bool
+		var at, presetName, gameKey string
+		for _, opt := range i.ApplicationCommandData().Options {
+			switch opt.Name {
+			case "thread":
+				thread = opt.BoolValue()
+			case "at":
+				at = opt.StringValue()
+			case "details":
+				details = opt.BoolValue()
+			case "preset":
+				presetName = opt.StringValue()
+			case "game":
+				gameKey = opt.StringValue()
+			}
+		}
+		if _, ok := q.presets[presetName]; presetName != "" && !ok {
+			respondEphemeral(s, i, tr("There is no preset named **%s**.", presetName))
+			return
+		}
+		g, ok := q.knownGames[gameKey]
+		if gameKey != "" && !ok {
+			respondEphemeral(s, i, tr("There is no game named **%s**.", gameKey))
+			return
+		}
+		p := q.presetLocked(presetName)
+		if presetName == "" && g.Size > 0 {
+			p.Size = g.Size
+		}
+		if at != "" {
+			q.scheduleOpenLocked(s, i, at, thread, p, gameKey)
+			return
+		}
+		q.preset = p
+		q.game = gameKey
+		if details {
+			if err := s.InteractionRespond(i.Interaction, detailsModal(thread)); err != nil {
+				logger.Error("error showing queue details modal", "err", err)
+			}
+			return
+		}
+
+		q.startQueueLocked(s, i, thread)
+
+	case "standby-close":
+		q.Lock()
+		defer q.Unlock()
+
+		if q.currentMsgID == "" && q.scheduledOpen != nil {
+			q.cancelScheduledOpenLocked(s, i)
+			return
+		}
+		if !q.canModerateLocked(s, i) {
+			respondEphemeral(s, i, tr("Only the queue creator, moderators or admins can do that."))
+			return
+		}
+		if q.currentMsgID == "" {
+			respondEphemeral(s, i, tr("No active queue to close."))
+			return
+		}
+		q.closeQueueLocked(s)
+
+		respondEphemeral(s, i, tr("Closing queue."))
+
+	case "standby-kick":
+		q.Lock()
+		defer q.Unlock()
+
+		if !q.canModerateLocked(s, i) {
+			respondEphemeral(s, i, tr("Only the queue creator, moderators or admins can do that."))
+			return
+		}
+		targetID := i.ApplicationCommandData().Options[0].UserValue(nil).ID
+		user := q.removeUserLocked(s, targetID)
+		if user == nil {
+			respondEphemeral(s, i, tr("<@%s> is not in the queue.", targetID))
+			return
+		}
+		q.recordActionLocked(user, actionKick)
+		if err := q.refreshLocked(s); err != nil {
+			logger.Error("error editing message after kick", "err", err)
+		}
+
+		respondEphemeral(s, i, tr("Removed <@%s> from the queue.", targetID))
+
+	case "result":
+		q.handleResultCommand(s, i)
+
+	case "stats":
+		if i.ApplicationCommandData().Options[0].Name == "server" {
+			q.handleServerStatsCommand(s, i)
+		} else {
+			q.handleStatsCommand(s, i)
+		}
+
+	case "feedback":
+		q.handleFeedbackCommand(s, i)
+
+	case "join":
+		q.handleJoinCommand(s, i)
+
+	case "history":
+		q.handleHistoryCommand(s, i)
+
+	case "standby-export":
+		q.handleExportCommand(s, i)
+
+	case "standby-backup":
+		q.handleBackupCommand(s, i)
+
+	case "standby-restore":
+		q.handleRestoreCommand(s, i)
+
+	case "notify":
+		q.handleNotifyCommand(s, i)
+
+	case "standby-swap":
+		q.handleSwapCommand(s, i)
+
+	case "standby-shuffle":
+		q.handleShuffleCommand(s, i)
+
+	case "standby-template":
+		q.handleTemplateCommand(s, i)
+
+	case "standby-promote":
+		q.handlePromoteCommand(s, i)
+
+	case "standby-preset":
+		q.handlePresetCommand(s, i)
+
+	case "standby-game":
+		q.handleGameCommand(s, i)
+
+	case "link":
+		q.handleLinkCommand(s, i)
+
+	case addToQueueCommand:
+		q.handleAddToQueueCommand(s, i)
+
+	case "standby-simulate":
+		q.handleSimulateCommand(s, i)
+
+	case "standby-event":
+		q.handleEventCommand(s, i)
+
+	case "standby-announce":
+		if !isAdmin(s, i) {
+			respondEphemeral(s, i, tr("Only admins can use this command."))
+			return
+		}
+
+		q.Lock()
+		defer q.Unlock()
+
+		q.pendingNotice = strings.TrimSpace(i.ApplicationCommandData().Options[0].StringValue())
+		respondEphemeral(s, i, tr("Notice will be shown on the next queue message."))
+
+	case "alias":
+		q.Lock()
+		defer q.Unlock()
+
+		var content string
+		sub := i.ApplicationCommandData().Options[0]
+		switch sub.Name {
+		case "set":
+			name := strings.TrimSpace(sub.Options[0].StringValue())
+			if name == "" {
+				content = tr("Alias cannot be empty.")
+				break
+			}
+			q.aliases[i.Member.User.ID] = name
+			content = tr("You will be shown as **%s** in the queue.", name)
+		case "clear":
+			delete(q.aliases, i.Member.User.ID)
+			content = tr("Alias cleared.")
+		}
+
+		if q.currentMsgID != "" {
+			if err := q.editQueueMessageLocked(s); err != nil {
+				logger.Error("error editing message after alias change", "err", err)
+			}
+		}
+
+		respondEphemeral(s, i, content)
+
+	case "setign":
+		q.Lock()
+		defer q.Unlock()
+
+		var ign string
+		if opts := i.ApplicationCommandData().Options; len(opts) > 0 {
+			ign = strings.TrimSpace(opts[0].StringValue())
+		}
+
+		content := tr("In-game ID removed.")
+		if ign != "" {
+			q.igns[i.Member.User.ID] = ign
+			content = tr("In-game ID set to `%s`.", ign)
+		} else {
+			delete(q.igns, i.Member.User.ID)
+		}
+
+		respondEphemeral(s, i, content)
+	}
+}
+
+// startQueueLocked opens a queue for the user who ran /standby, optionally in
+// a new thread.
+//
+// lock must be held
+func (q *queueState) startQueueLocked(s *discordgo.Session, i *discordgo.InteractionCreate, thread bool) {
+	logger := interactionLogger(i)
+	if thread {
+		if err := q.startThreadLocked(s); err != nil {
+			logger.Error("error starting queue thread", "err", err)
+			respondEphemeral(s, i, tr("Couldn't create a thread for the queue."))
+			q.details = queueDetails{}
+			return
+		}
+	}
+
+	if err := q.openQueueLocked(s); err != nil {
+		logger.Error("error opening queue", "err", err)
+		q.threadID = ""
+		q.details = queueDetails{}
+		return
+	}
+	q.creatorID = i.Member.User.ID
+
+	respondEphemeral(s, i, tr("Starting queue."))
+}
+
+// lock must be held
+func (q *queueState) openQueueLocked(s *discordgo.Session) error {
+	q.startTime = time.Now()
+	q.fillRecorded = false
+	q.lastWaitlist = nil
+	q.chatCount = 0
+	q.chatStart = time.Time{}
+	q.armReservationLocked(s)
+	q.touchIdleLocked(s)
+	if q.pendingNotice != "" {
+		q.notice = q.pendingNotice
+		q.pendingNotice = ""
+	}
+
+	// Reuse the channel's closed queue message rather than posting another.
+	if q.closedMsgID != "" && q.threadID == "" {
+		q.currentMsgID = q.closedMsgID
+		q.closedMsgID = ""
+		err := q.editQueueMessageLocked(s)
+		if err == nil {
+			q.announceLocked(s, tr("Queue open in <#%s> — %d/%d", q.channelLocked(), len(q.users), q.preset.Size))
+			return nil
+		}
+		slog.Warn("error reopening closed queue message, posting a new one", "err", err, "message", q.currentMsgID)
+		q.currentMsgID = ""
+	}
+	if err := q.sendQueueMessageLocked(s); err != nil {
+		return err
+	}
+	q.announceLocked(s, tr("Queue open in <#%s> — %d/%d", q.channelLocked(), len(q.users), q.preset.Size))
+	return nil
+}
+
+// sendQueueMessageLocked posts a new queue message with the current state
+// and tracks it as the active one.
+//
+// lock must be held
+func (q *queueState) sendQueueMessageLocked(s *discordgo.Session) error {
+	msg, err := sendMessage(s, q.channelLocked(), &discordgo.MessageSend{
+		Embeds:     []*discordgo.MessageEmbed{q.queueEmbedLocked()},
+		Components: q.queueComponentsLocked(),
+	})
+	if err != nil {
+		return err
+	}
+	q.currentMsgID = msg.ID
+	return nil
+}
+
+// editClosedMessage turns a queue message into the closed state, leaving
+// only the Open button enabled. If waitlisted users were left behind, a
+// button to reopen with them is offered too.
+func editClosedMessage(s *discordgo.Session, msgID string, waitlisted int) error {
+	buttons := []discordgo.MessageComponent{
+		discordgo.Button{
+			Label:    tr("Join"),
+			Style:    discordgo.PrimaryButton,
+			CustomID: "join_queue",
+			Disabled: true,
+		},
+		discordgo.Button{
+			Label:    tr("Leave"),
+			Style:    discordgo.DangerButton,
+			CustomID: "leave_queue",
+			Disabled: true,
+		},
+		discordgo.Button{
+			Label:    tr("Open"),
+			Style:    discordgo.SecondaryButton,
+			CustomID: "open_queue",
+		},
+	}
+	if waitlisted > 0 {
+		buttons = append(buttons, discordgo.Button{
+			Label:    tr("Reopen with waitlist (%d)", waitlisted),
+			Style:    discordgo.SecondaryButton,
+			CustomID: "reopen_waitlist",
+		})
+	}
+	_, err := editMessage(s, &discordgo.MessageEdit{
+		ID:      msgID,
+		Channel: ChannelID,
+		Embeds: []*discordgo.MessageEmbed{
+			{
+				Type:        discordgo.EmbedTypeRich,
+				Title:       queueTitle,
+				Color:       0x0099FF,
+				Description: tr("Queue is closed"),
+			},
+		},
+		Components: []discordgo.MessageComponent{
+			discordgo.ActionsRow{Components: buttons},
+		},
+	})
+	return err
+}
+
+// lock must be held
+func (q *queueState) closeQueueLocked(s *discordgo.Session) {
+	q.recordEventLocked(actionClose)
+	if q.threadID != "" {
+		// Reset first so the notification is cleared before the thread is
+		// archived.
+		threadID, msgID := q.threadID, q.currentMsgID
+		q.resetLocked(s)
+		closeThread(s, threadID, msgID)
+		return
+	}
+
+	var waitlist []*discordgo.User
+	if len(q.users) > q.preset.Size {
+		waitlist = append(waitlist, q.users[q.preset.Size:]...)
+	}
+	if err := editClosedMessage(s, q.currentMsgID, len(waitlist)); err != nil {
+		slog.Error("error editing message closing queue", "err", err, "message", q.currentMsgID)
+	} else {
+		q.closedMsgID = q.currentMsgID
+	}
+	q.resetLocked(s)
+	q.lastWaitlist = waitlist
+}
+
+// resetLocked clears all per-queue state, retracting the notification and
+// watcher roles.
+//
+// lock must be held
+func (q *queueState) resetLocked(s *discordgo.Session) {
+	q.clearNotificationLocked(s)
+	q.clearCompositionLocked(s)
+	q.clearAnnouncementsLocked(s)
+	q.stopAlertLocked()
+	q.stopReservationLocked()
+	q.stopIdleLocked(s)
+	for userID := range q.offers {
+		q.cancelOfferLocked(s, userID)
+	}
+
+	q.currentMsgID = ""
+	q.creatorID = ""
+	q.threadID = ""
+	q.frozen = false
+	q.eventID = ""
+	q.notice = ""
+	q.details = queueDetails{}
+	q.feed = nil
+	for _, hook := range q.hooks {
+		hook.OnClose(s, q.users)
+	}
+	q.users = nil
+	q.spectators = nil
+	q.subs = nil
+	q.subCalls = 0
+	clear(q.regulars)
+	clear(q.joinedAt)
+}
+
+// lock must be held
+func (q *queueState) queueComponentsLocked() []discordgo.MessageComponent {
+	buttons := []discordgo.MessageComponent{
+		discordgo.Button{
+			Label:    tr("Join"),
+			Style:    discordgo.PrimaryButton,
+			CustomID: "join_queue",
+			Disabled: q.frozen,
+		},
+		discordgo.Button{
+			Label:    tr("Leave"),
+			Style:    discordgo.DangerButton,
+			CustomID: "leave_queue",
+			Disabled: q.frozen,
+		},
+		discordgo.Button{
+			Label:    tr("Close"),
+			Style:    discordgo.SecondaryButton,
+			CustomID: "close_queue",
+		},
+	}
+	if q.frozen {
+		buttons = append(buttons, discordgo.Button{
+			Label:    tr("Unpause"),
+			Style:    discordgo.SuccessButton,
+			CustomID: "unpause_queue",
+		})
+	} else {
+		buttons = append(buttons, discordgo.Button{
+			Label:    tr("Pause"),
+			Style:    discordgo.SecondaryButton,
+			CustomID: "pause_queue",
+		})
+	}
+	standby := []discordgo.MessageComponent{
+		discordgo.Button{
+			Label:    tr("Sub"),
+			Style:    discordgo.SecondaryButton,
+			CustomID: "sub_queue",
+		},
+		discordgo.Button{
+			Label:    tr("Spectate"),
+			Style:    discordgo.SecondaryButton,
+			CustomID: "spectate_queue",
+		},
+	}
+	// The picker is also offered on joining, but users added by someone
+	// else or promoted without an offer need a way to reach it.
+	if len(GameRoles) > 0 {
+		standby = append(standby, discordgo.Button{
+			Label:    tr("Roles"),
+			Style:    discordgo.SecondaryButton,
+			CustomID: "open_role_picker",
+		})
+	}
+	rows := []discordgo.MessageComponent{
+		discordgo.ActionsRow{Components: buttons},
+		discordgo.ActionsRow{Components: standby},
+	}
+	if len(q.users) >= 2*q.preset.TeamSize {
+		rows = append(rows, discordgo.ActionsRow{
+			Components: []discordgo.MessageComponent{
+				discordgo.Button{
+					Label:    tr("Split Teams"),
+					Style:    discordgo.SuccessButton,
+					CustomID: "split_teams",
+				},
+				discordgo.Button{
+					Label:    tr("Draft"),
+					Style:    discordgo.SuccessButton,
+					CustomID: "start_draft",
+				},
+			},
+		})
+	}
+	return rows
+}
+
+// lock must be held
+func (q *queueState) editQueueMessageLocked(s *discordgo.Session) error {
+	_, err := editMessage(s, &discordgo.MessageEdit{
+		ID:         q.currentMsgID,
+		Channel:    q.channelLocked(),
+		Embeds:     []*discordgo.MessageEmbed{q.queueEmbedLocked()},
+		Components: q.queueComponentsLocked(),
+	})
+	return err
+}
+
+func (q *queueState) handleButtonClick(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	logger := interactionLogger(i)
+	logger.Debug("handling button click", "button", i.MessageComponentData().CustomID)
+
+	// joinBlocked may fetch the member from the API, so it is checked
+	// before taking the lock.
+	var blocked string
+	if i.MessageComponentData().CustomID == "join_queue" {
+		blocked = joinBlocked(s, i.Member.User)
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if name, ok := strings.CutPrefix(i.MessageComponentData().CustomID, vetoPrefix); ok {
+		q.handleVetoLocked(s, i, name)
+		return
+	}
+
+	switch i.MessageComponentData().CustomID {
+	case "split_teams":
+		q.handleSplitTeamsLocked(s, i)
+		return
+	case "start_draft":
+		q.startDraftLocked(s, i)
+		return
+	case "draft_pick":
+		q.draftPickLocked(s, i)
+		return
+	case "pick_game_role":
+		q.handlePickGameRoleLocked(s, i)
+		return
+	case "open_role_picker":
+		q.respondRolePickerLocked(s, i, tr("Pick the roles you can play."))
+		return
+	case "post_lobby_code":
+		q.handlePostLobbyCodeLocked(s, i)
+		return
+	case "accept_promotion", "decline_promotion":
+		q.handlePromotionLocked(s, i, i.MessageComponentData().CustomID == "accept_promotion")
+		return
+	case "pause_queue", "unpause_queue":
+		q.handleFreezeLocked(s, i, i.MessageComponentData().CustomID == "pause_queue")
+		return
+	case "close_queue":
+		if !q.canModerateLocked(s, i) {
+			respondEphemeral(s, i, tr("Only the queue creator, moderators or admins can do that."))
+			return
+		}
+		q.closeQueueLocked(s)
+		s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+			Type: discordgo.InteractionResponseUpdateMessage,
+		})
+		return
+	case "open_queue", "reopen_waitlist":
+		if q.currentMsgID != "" {
+			respondEphemeral(s, i, tr("There is already an existing queue."))
+			return
+		}
+
+		// Bring back the previous waitlist ahead of the opener so they keep
+		// their place
+		if i.MessageComponentData().CustomID == "reopen_waitlist" {
+			for _, user := range q.lastWaitlist {
+				q.addUserLocked(s, user)
+			}
+		}
+
+		// Add the user who opened queue
+		if q.positionLocked(i.Member.User.ID) == 0 {
+			q.addUserLocked(s, i.Member.User)
+		}
+		q.recordActionLocked(i.Member.User, actionJoin)
+
+		// Reopen in the message that was clicked
+		q.closedMsgID = i.Message.ID
+		if err := q.openQueueLocked(s); err != nil {
+			logger.Error("error reopening queue", "err", err)
+		}
+		q.creatorID = i.Member.User.ID
+
+		s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+			Type: discordgo.InteractionResponseUpdateMessage,
+		})
+		return
+	case "join_queue":
+		if q.frozen {
+			respondEphemeral(s, i, tr("The queue is paused."))
+			return
+		}
+		if q.throttledLocked(i.Member.User.ID) {
+			respondEphemeral(s, i, tr("Slow down! Try again in a moment."))
+			return
+		}
+		if pos := q.positionLocked(i.Member.User.ID); pos > 0 {
+			respondEphemeral(s, i, tr("You're already in the queue at position %d.", pos))
+			return
+		}
+		if blocked != "" {
+			respondEphemeral(s, i, blocked)
+			return
+		}
+		q.addUserLocked(s, i.Member.User)
+		q.recordActionLocked(i.Member.User, actionJoin)
+	case "leave_queue":
+		if q.frozen {
+			respondEphemeral(s, i, tr("The queue is paused."))
+			return
+		}
+		if q.throttledLocked(i.Member.User.ID) {
+			respondEphemeral(s, i, tr("Slow down! Try again in a moment."))
+			return
+		}
+		if q.removeUserLocked(s, i.Member.User.ID) == nil {
+			respondEphemeral(s, i, tr("You're not in the queue."))
+			return
+		}
+		q.recordActionLocked(i.Member.User, actionLeave)
+	case "spectate_queue":
+		if q.throttledLocked(i.Member.User.ID) {
+			respondEphemeral(s, i, tr("Slow down! Try again in a moment."))
+			return
+		}
+		if q.positionLocked(i.Member.User.ID) > 0 {
+			respondEphemeral(s, i, tr("You're in the queue. Leave it first to spectate."))
+			return
+		}
+		if q.toggleSpectatorLocked(i.Member.User) {
+			q.removeSubLocked(i.Member.User.ID)
+		}
+	case "sub_queue":
+		if q.throttledLocked(i.Member.User.ID) {
+			respondEphemeral(s, i, tr("Slow down! Try again in a moment."))
+			return
+		}
+		if q.positionLocked(i.Member.User.ID) > 0 {
+			respondEphemeral(s, i, tr("You're in the queue. Leave it first to sub."))
+			return
+		}
+		if q.toggleSubLocked(i.Member.User) {
+			q.removeSpectatorLocked(i.Member.User.ID)
+		}
+	}
+	q.scheduleRefreshLocked(s)
+	if incidents.degraded() {
+		respondEphemeral(s, i, tr("Discord is having issues. Your click is saved and the queue will update once it recovers."))
+		return
+	}
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseDeferredMessageUpdate,
+	})
+
+	if i.MessageComponentData().CustomID == "join_queue" {
+		q.followupJoinedLocked(s, i)
+	}
+	if q.firstVisitLocked(i.Member.User.ID) {
+		followupEphemeral(s, i, onboardingTips(q.preset.Size))
+	}
+}
+
+// throttledLocked reports whether the user clicked Join or Leave less than
+// ButtonCooldown ago, and otherwise records the click.
+//
+// lock must be held
+func (q *queueState) throttledLocked(userID string) bool {
+	now := time.Now()
+	if last, ok := q.lastClick[userID]; ok && now.Sub(last) < ButtonCooldown {
+		return true
+	}
+	q.lastClick[userID] = now
+	return false
+}
+
+// positionTextLocked tells a user where they landed, so those past the
+// stack know they are waitlisted.
+//
+// lock must be held
+func (q *queueState) positionTextLocked(userID string) string {
+	pos := q.positionLocked(userID)
+	if pos <= q.preset.Size {
+		return tr("You're #%d in the queue.", pos)
+	}
+	return tr("You're #%d — #%d on the waitlist.", pos, pos-q.preset.Size)
+}
+
+// positionLocked returns the 1-based queue position of the user, or 0 if
+// they are not queued.
+//
+// lock must be held
+func (q *queueState) positionLocked(userID string) int {
+	for idx, user := range q.users {
+		if user.ID == userID {
+			return idx + 1
+		}
+	}
+	return 0
+}
+
+// lock must be held
+func (q *queueState) addUserLocked(s *discordgo.Session, user *discordgo.User) {
+	q.users = append(q.users, user)
+	q.removeSpectatorLocked(user.ID)
+	q.seatSubLocked(s, user)
+	q.joinedAt[user.ID] = time.Now()
+	q.statsLocked(user.ID).Queued++
+	q.fetchRankLocked(s, user.ID)
+	q.placeReservedLocked(s)
+	q.touchIdleLocked(s)
+	for _, hook := range q.hooks {
+		hook.OnJoin(s, user)
+	}
+}
+
+// removeUserLocked removes the user with the given ID from the queue,
+// returning them, or nil if they were not queued.
+//
+// lock must be held
+func (q *queueState) removeUserLocked(s *discordgo.Session, userID string) *discordgo.User {
+	for idx, user := range q.users {
+		if user.ID == userID {
+			q.users = append(q.users[:idx], q.users[idx+1:]...)
+			delete(q.joinedAt, userID)
+			q.cancelOfferLocked(s, userID)
+			if idx < q.preset.Size {
+				q.callSubsLocked(s, user)
+				q.promoteLocked(s)
+			}
+			q.touchIdleLocked(s)
+			for _, hook := range q.hooks {
+				hook.OnLeave(s, user)
+			}
+			return user
+		}
+	}
+	return nil
+}
+
+// updateNotificationLocked sends the ready notification once the queue
+// reaches the preset's size and retracts it if the queue drops below again. Users
+// joining an already full queue leave the existing notification in place.
+//
+// lock must be held
+func (q *queueState) updateNotificationLocked(s *discordgo.Session) {
+	ready := len(q.users) >= q.preset.Size && !q.reservationHeldLocked()
+	if ready && q.notifyMsgID == "" {
+		missing := q.missingRolesLocked(q.users[:q.preset.Size])
+		q.updateCompositionLocked(s, missing)
+		// Hold the ping until promoted waitlisters have accepted their spot.
+		ready = len(missing) == 0 && len(q.offers) == 0
+	} else if !ready {
+		q.clearCompositionLocked(s)
+	}
+	switch {
+	case ready && q.notifyMsgID == "":
+		usernames := make([]string, len(q.users))
+		for i, user := range q.users {
+			usernames[i] = q.readyMentionLocked(user)
+		}
+
+		content := fmt.Sprintf("%s %s", tr(currentTheme(time.Now()).Ready), strings.Join(usernames, ", "))
+		if tmpl, ok := q.templates[templateReady]; ok {
+			content = renderTemplate(tmpl, q.queueNameLocked(), 0, strings.Join(usernames, ", "))
+		}
+		msg := &discordgo.MessageSend{
+			Content:    content + q.lobbyListLocked(),
+			Components: []discordgo.MessageComponent{lobbyCodeRow()},
+		}
+		if len(MapPool) > 1 {
+			q.veto = newMapVeto(msg.Content)
+			msg.Content, msg.Components = q.vetoMessageLocked()
+		}
+
+		m, err := sendMessage(s, q.channelLocked(), msg)
+		if err != nil {
+			slog.Error("error sending notification message", "err", err)
+			q.veto = nil
+			return
+		}
+		q.notifyMsgID = m.ID
+		q.recordEventLocked(actionFill)
+		q.lastStack = append([]*discordgo.User(nil), q.users[:q.preset.Size]...)
+		q.recordGamesLocked()
+		q.recordFilledLocked(q.lastStack)
+		q.recordFillLocked()
+		q.announceLocked(s, tr("Queue in <#%s> is full — %d/%d, time for a game!", q.channelLocked(), q.preset.Size, q.preset.Size))
+		for _, hook := range q.hooks {
+			hook.OnFull(s, q.users[:q.preset.Size])
+		}
+	case !ready && q.notifyMsgID != "":
+		q.clearNotificationLocked(s)
+	case ready:
+		q.syncVetoLocked(s)
+	}
+}
+
+// lock must be held
+func (q *queueState) clearNotificationLocked(s *discordgo.Session) {
+	if q.notifyMsgID == "" {
+		return
+	}
+	msgID := q.notifyMsgID
+	q.notifyMsgID = ""
+	q.veto = nil
+	q.subCalls = 0
+	if err := deleteMessage(s, q.channelLocked(), msgID); err != nil {
+		slog.Error("error deleting notification message", "err", err, "message", msgID)
+	}
+}
+
+// handleMessageDelete reposts the queue message with its current state if a
+// moderator deletes it, so the queue isn't left open without any buttons.
+func (q *queueState) handleMessageDelete(s *discordgo.Session, m *discordgo.MessageDelete) {
+	q.Lock()
+	defer q.Unlock()
+
+	if m.ID == "" {
+		return
+	}
+	if m.ChannelID == ChannelID && m.ID == q.closedMsgID {
+		q.closedMsgID = ""
+		return
+	}
+	if m.ChannelID != q.channelLocked() || m.ID != q.currentMsgID {
+		return
+	}
+	slog.Info("queue message deleted, reposting", "message", m.ID)
+	if err := q.sendQueueMessageLocked(s); err != nil {
+		slog.Error("error reposting deleted queue message", "err", err)
+		q.resetLocked(s)
+	}
+}
+
+// handleMemberRemove drops users who leave the guild from the queue so it
+// never shows members who can no longer play.
+func (q *queueState) handleMemberRemove(s *discordgo.Session, m *discordgo.GuildMemberRemove) {
+	if m.GuildID != GuildID || m.User == nil {
+		return
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	user := q.removeUserLocked(s, m.User.ID)
+	if user == nil {
+		return
+	}
+	q.recordActionLocked(user, actionLeave)
+	if err := q.refreshLocked(s); err != nil {
+		slog.Error("error editing message after member left guild", "err", err, "user", user.ID)
+	}
+}
+
+// scheduleRefreshLocked coalesces queue message edits: the first change
+// arms a timer and every change until it fires is covered by a single
+// refresh with the final state.
+//
+// lock must be held
+func (q *queueState) scheduleRefreshLocked(s *discordgo.Session) {
+	if q.refreshTimer != nil {
+		return
+	}
+	delay := editDebounce
+	if incidents.degraded() {
+		delay = degradedRetry
+	}
+	q.refreshTimer = time.AfterFunc(delay, func() {
+		q.Lock()
+		defer q.Unlock()
+
+		q.refreshTimer = nil
+		if q.currentMsgID == "" {
+			return
+		}
+		// Hold the edit back until Discord recovers; the state is kept in
+		// memory and rendered in full once it does.
+		if incidents.degraded() {
+			q.scheduleRefreshLocked(s)
+			return
+		}
+		if err := q.refreshLocked(s); err != nil {
+			slog.Error("error editing message handling button click", "err", err, "message", q.currentMsgID)
+		}
+	})
+}
+
+// refreshLocked re-renders the queue message after the user list changed,
+// closing the queue if it emptied and sending or retracting the ready
+// notification.
+//
+// lock must be held
+func (q *queueState) refreshLocked(s *discordgo.Session) error {
+	if err := q.editQueueMessageLocked(s); err != nil {
+		return err
+	}
+
+	// Close queue is a user leaving would leave it at 0
+	if len(q.users) == 0 {
+		q.closeQueueLocked(s)
+	}
+
+	q.updateNotificationLocked(s)
+	q.updateAlertLocked(s)
+	return nil
 }
@@ -0,0 +1,151 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+	"sync"
+	"testing"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// fakeDiscord answers Discord API requests with success, recording them so
+// tests can count what the bot sent.
+type fakeDiscord struct {
+	mu       sync.Mutex
+	requests []string
+	nextID   int
+	// responses holds canned response bodies keyed by method and path
+	// suffix, e.g. "GET /users/1".
+	responses map[string]string
+}
+
+func (f *fakeDiscord) RoundTrip(r *http.Request) (*http.Response, error) {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+
+	f.requests = append(f.requests, r.Method+" "+r.URL.Path)
+	resp := &http.Response{
+		StatusCode: http.StatusNoContent,
+		Header:     http.Header{"Content-Type": {"application/json"}},
+		Body:       io.NopCloser(strings.NewReader("")),
+		Request:    r,
+	}
+	for key, body := range f.responses {
+		method, suffix, _ := strings.Cut(key, " ")
+		if r.Method == method && strings.HasSuffix(r.URL.Path, suffix) {
+			resp.StatusCode = http.StatusOK
+			resp.Body = io.NopCloser(strings.NewReader(body))
+			return resp, nil
+		}
+	}
+	if r.Method != http.MethodDelete {
+		f.nextID++
+		resp.StatusCode = http.StatusOK
+		resp.Body = io.NopCloser(strings.NewReader(fmt.Sprintf(`{"id":"m%d","channel_id":"%s"}`, f.nextID, ChannelID)))
+	}
+	return resp, nil
+}
+
+// count returns how many requests with the given method had a path ending
+// in suffix.
+func (f *fakeDiscord) count(method, suffix string) int {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+
+	var n int
+	for _, req := range f.requests {
+		m, path, _ := strings.Cut(req, " ")
+		if m == method && strings.HasSuffix(path, suffix) {
+			n++
+		}
+	}
+	return n
+}
+
+func newFakeSession(t *testing.T) (*discordgo.Session, *fakeDiscord) {
+	t.Helper()
+	s, err := discordgo.New("Bot test")
+	if err != nil {
+		t.Fatal(err)
+	}
+	fake := &fakeDiscord{}
+	s.Client = &http.Client{Transport: fake}
+	s.MaxRestRetries = 0
+	return s, fake
+}
+
+func TestNotificationLifecycle(t *testing.T) {
+	oldChannel, oldTimeout := ChannelID, PromotionTimeout
+	t.Cleanup(func() { ChannelID, PromotionTimeout = oldChannel, oldTimeout })
+	ChannelID = "chan"
+	PromotionTimeout = 0
+
+	s, fake := newFakeSession(t)
+	q := newQueueState()
+	q.currentMsgID = "queue"
+
+	users := make([]*discordgo.User, 7)
+	for idx := range users {
+		users[idx] = &discordgo.User{ID: fmt.Sprintf("u%d", idx+1), Username: fmt.Sprintf("user%d", idx+1)}
+	}
+	join := func(n int) func() {
+		return func() { q.addUserLocked(s, users[n]) }
+	}
+	leave := func(n int) func() {
+		return func() { q.removeUserLocked(s, users[n].ID) }
+	}
+
+	// Sends are counted across every message the bot posts; this flow posts
+	// nothing but ready pings.
+	steps := []struct {
+		name string
+		do   func()
+		// close closes the queue instead of refreshing it.
+		close       bool
+		wantNotify  bool
+		wantSends   int
+		wantDeletes int
+	}{
+		{name: "one short", do: func() {
+			for n := 0; n < 4; n++ {
+				join(n)()
+			}
+		}},
+		{name: "join to full", do: join(4), wantNotify: true, wantSends: 1},
+		{name: "waitlisted join", do: join(5), wantNotify: true, wantSends: 1},
+		{name: "stack leave with waitlist", do: leave(0), wantNotify: true, wantSends: 1},
+		{name: "leave below stack", do: leave(1), wantSends: 1, wantDeletes: 1},
+		{name: "refresh while short", do: func() {}, wantSends: 1, wantDeletes: 1},
+		{name: "join to full again", do: join(6), wantNotify: true, wantSends: 2, wantDeletes: 1},
+		{name: "close", close: true, wantSends: 2, wantDeletes: 2},
+		{name: "clear after close", do: func() { q.clearNotificationLocked(s) }, close: true, wantSends: 2, wantDeletes: 2},
+	}
+	for _, step := range steps {
+		q.Lock()
+		if step.do != nil {
+			step.do()
+		}
+		if step.close {
+			if q.currentMsgID != "" {
+				q.closeQueueLocked(s)
+			}
+		} else if err := q.refreshLocked(s); err != nil {
+			t.Fatalf("%s: refreshLocked() = %v", step.name, err)
+		}
+		notifyMsgID := q.notifyMsgID
+		q.Unlock()
+
+		if got := notifyMsgID != ""; got != step.wantNotify {
+			t.Errorf("%s: notification set = %v, want %v", step.name, got, step.wantNotify)
+		}
+		if got := fake.count(http.MethodPost, "/channels/chan/messages"); got != step.wantSends {
+			t.Errorf("%s: sent %d messages, want %d", step.name, got, step.wantSends)
+		}
+		if got := fake.count(http.MethodDelete, ""); got != step.wantDeletes {
+			t.Errorf("%s: deleted %d messages, want %d", step.name, got, step.wantDeletes)
+		}
+	}
+}
@@ -0,0 +1,196 @@
+package main
+
+import (
+	"math/rand"
+	"os"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// addToQueueCommand is the user context menu entry for adding a member to
+// the queue.
+const addToQueueCommand = "Add to standby queue"
+
+// ModeratorRoleID is an optional role allowed to close queues and kick
+// users, alongside admins and members with Manage Messages.
+var ModeratorRoleID = os.Getenv("STANDBY_MODERATOR_ROLE_ID")
+
+// canManage reports whether the user may rearrange the queue: admins and
+// whoever opened it.
+//
+// lock must be held
+func (q *queueState) canManageLocked(s *discordgo.Session, i *discordgo.InteractionCreate) bool {
+	return (q.creatorID != "" && q.creatorID == i.Member.User.ID) || isAdmin(s, i)
+}
+
+// canModerateLocked reports whether the user may close the queue or kick
+// others from it: whoever opened it, members with Manage Messages in the
+// channel, moderators and admins.
+//
+// lock must be held
+func (q *queueState) canModerateLocked(s *discordgo.Session, i *discordgo.InteractionCreate) bool {
+	return q.canManageLocked(s, i) ||
+		i.Member.Permissions&discordgo.PermissionManageMessages != 0 ||
+		hasRole(s, i.Member.User.ID, ModeratorRoleID)
+}
+
+func (q *queueState) handleSwapCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	var first, second string
+	for _, opt := range i.ApplicationCommandData().Options {
+		switch opt.Name {
+		case "first":
+			first = opt.UserValue(nil).ID
+		case "second":
+			second = opt.UserValue(nil).ID
+		}
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if !q.canManageLocked(s, i) {
+		respondEphemeral(s, i, tr("Only admins or the queue creator can use this command."))
+		return
+	}
+	a, b := q.positionLocked(first), q.positionLocked(second)
+	switch {
+	case a == 0:
+		respondEphemeral(s, i, tr("<@%s> is not in the queue.", first))
+		return
+	case b == 0:
+		respondEphemeral(s, i, tr("<@%s> is not in the queue.", second))
+		return
+	case a == b:
+		respondEphemeral(s, i, tr("Pick two different users."))
+		return
+	}
+
+	q.users[a-1], q.users[b-1] = q.users[b-1], q.users[a-1]
+	q.dropStaleOffersLocked(s)
+	if err := q.refreshLocked(s); err != nil {
+		interactionLogger(i).Error("error editing message after swap", "err", err)
+	}
+
+	respondEphemeral(s, i, tr("Swapped <@%s> (now #%d) and <@%s> (now #%d).", first, b, second, a))
+}
+
+// handlePromoteCommand moves a waitlisted user into the last slot of the
+// stack, bumping whoever held it to the top of the waitlist.
+func (q *queueState) handlePromoteCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+	targetID := i.ApplicationCommandData().Options[0].UserValue(nil).ID
+
+	q.Lock()
+	defer q.Unlock()
+
+	pos := q.positionLocked(targetID)
+	switch {
+	case pos == 0:
+		respondEphemeral(s, i, tr("<@%s> is not in the queue.", targetID))
+		return
+	case pos <= q.preset.Size:
+		respondEphemeral(s, i, tr("<@%s> is already in the stack.", targetID))
+		return
+	}
+
+	slot := q.preset.Size - 1
+	user, bumped := q.users[pos-1], q.users[slot]
+	copy(q.users[slot+1:pos], q.users[slot:pos-1])
+	q.users[slot] = user
+	q.dropStaleOffersLocked(s)
+	// The ready notification has already gone out; keep /result in line
+	// with who is actually playing.
+	if q.notifyMsgID != "" && len(q.lastStack) == q.preset.Size {
+		q.lastStack[slot] = user
+	}
+
+	q.recordActionLocked(user, actionPromote)
+	if err := q.refreshLocked(s); err != nil {
+		interactionLogger(i).Error("error editing message after promote", "err", err)
+	}
+
+	content := tr("%s was promoted into the stack. %s moved to the top of the waitlist.", mention(user), mention(bumped))
+	if _, err := sendMessage(s, q.channelLocked(), &discordgo.MessageSend{Content: content}); err != nil {
+		interactionLogger(i).Error("error sending promotion message", "err", err)
+	}
+	respondEphemeral(s, i, tr("Promoted <@%s>.", targetID))
+}
+
+// handleShuffleCommand randomly reorders the whole queue, deciding who is in
+// the stack and who is waitlisted. Once the ready ping is out the stack is
+// settled, so shuffling is refused.
+func (q *queueState) handleShuffleCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	q.Lock()
+	defer q.Unlock()
+
+	if !q.canManageLocked(s, i) {
+		respondEphemeral(s, i, tr("Only admins or the queue creator can use this command."))
+		return
+	}
+	if q.currentMsgID == "" {
+		respondEphemeral(s, i, tr("There is no open queue. Use /standby to start one."))
+		return
+	}
+	if q.notifyMsgID != "" {
+		respondEphemeral(s, i, tr("The stack has already been pinged, so the queue can't be shuffled."))
+		return
+	}
+
+	rand.Shuffle(len(q.users), func(a, b int) {
+		q.users[a], q.users[b] = q.users[b], q.users[a]
+	})
+	q.dropStaleOffersLocked(s)
+	q.recordActionLocked(i.Member.User, actionShuffle)
+	if err := q.refreshLocked(s); err != nil {
+		interactionLogger(i).Error("error editing message after shuffle", "err", err)
+	}
+
+	respondEphemeral(s, i, tr("Shuffled the queue."))
+}
+
+// handleAddToQueueCommand adds the right-clicked member to the queue the
+// same way the Join button would.
+func (q *queueState) handleAddToQueueCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	data := i.ApplicationCommandData()
+	user := data.Resolved.Users[data.TargetID]
+	// Checked before locking, as for the Join button, since it may call
+	// the API.
+	blocked := joinBlocked(s, user)
+
+	q.Lock()
+	defer q.Unlock()
+
+	if !q.canModerateLocked(s, i) {
+		respondEphemeral(s, i, tr("Only the queue creator, moderators or admins can do that."))
+		return
+	}
+	switch {
+	case q.currentMsgID == "":
+		respondEphemeral(s, i, tr("There is no open queue. Use /standby to start one."))
+		return
+	case q.frozen:
+		respondEphemeral(s, i, tr("The queue is paused."))
+		return
+	case user.Bot:
+		respondEphemeral(s, i, tr("Bots can't join the queue."))
+		return
+	case q.positionLocked(user.ID) > 0:
+		respondEphemeral(s, i, tr("%s is already in the queue.", q.nameLocked(user)))
+		return
+	}
+	if blocked != "" {
+		respondEphemeral(s, i, blocked)
+		return
+	}
+
+	q.addUserLocked(s, user)
+	q.recordActionLocked(user, actionJoin)
+	if err := q.refreshLocked(s); err != nil {
+		interactionLogger(i).Error("error editing message after adding user", "err", err)
+	}
+
+	respondEphemeral(s, i, tr("Added <@%s> to the queue as #%d.", user.ID, q.positionLocked(user.ID)))
+}
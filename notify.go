@@ -0,0 +1,54 @@
+package main
+
+import (
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// maxMute is the longest a user can mute ready pings for.
+const maxMute = 7 * 24 * time.Hour
+
+// lock must be held
+func (q *queueState) mutedLocked(userID string) bool {
+	until, ok := q.mutedUntil[userID]
+	if ok && time.Now().After(until) {
+		delete(q.mutedUntil, userID)
+		return false
+	}
+	return ok
+}
+
+// readyMentionLocked mentions the user in the ready notification, or just
+// names them if they muted ready pings.
+//
+// lock must be held
+func (q *queueState) readyMentionLocked(user *discordgo.User) string {
+	if q.mutedLocked(user.ID) {
+		return "**" + q.nameLocked(user) + "**"
+	}
+	return mention(user)
+}
+
+func (q *queueState) handleNotifyCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	sub := i.ApplicationCommandData().Options[0]
+	userID := i.Member.User.ID
+
+	q.Lock()
+	defer q.Unlock()
+
+	switch sub.Name {
+	case "mute":
+		d, err := time.ParseDuration(sub.Options[0].StringValue())
+		if err != nil || d <= 0 || d > maxMute {
+			respondEphemeral(s, i, tr("Use a duration like 30m or 2h, up to %s.", maxMute))
+			return
+		}
+		until := time.Now().Add(d)
+		q.mutedUntil[userID] = until
+		respondEphemeral(s, i, tr("You won't be pinged when the stack is ready until <t:%d:t>.", until.Unix()))
+	case "unmute":
+		delete(q.mutedUntil, userID)
+		respondEphemeral(s, i, tr("You'll be pinged when the stack is ready again."))
+	}
+}
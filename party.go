@@ -0,0 +1,73 @@
+package main
+
+import (
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// maxPartySize includes the user running /join.
+const maxPartySize = stackSize
+
+func (q *queueState) handleJoinCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	party := []*discordgo.User{i.Member.User}
+	for _, opt := range i.ApplicationCommandData().Options {
+		friend := opt.UserValue(s)
+		if friend.Bot {
+			respondEphemeral(s, i, tr("Bots can't join the queue."))
+			return
+		}
+		var dup bool
+		for _, user := range party {
+			dup = dup || user.ID == friend.ID
+		}
+		if !dup {
+			party = append(party, friend)
+		}
+	}
+
+	if len(party) == 1 {
+		respondEphemeral(s, i, tr("Pick at least one friend to queue with, or use the Join button."))
+		return
+	}
+	for _, user := range party {
+		if reason := joinBlocked(s, user); reason != "" {
+			respondEphemeral(s, i, reason)
+			return
+		}
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if q.currentMsgID == "" {
+		respondEphemeral(s, i, tr("There is no open queue. Use /standby to start one."))
+		return
+	}
+	if q.frozen {
+		respondEphemeral(s, i, tr("The queue is paused."))
+		return
+	}
+	for _, user := range party {
+		if q.positionLocked(user.ID) > 0 {
+			respondEphemeral(s, i, tr("%s is already in the queue.", q.nameLocked(user)))
+			return
+		}
+	}
+	if free := q.preset.Size - len(q.users); free > 0 && free < len(party) {
+		respondEphemeral(s, i, tr("Only %d spots are left in the stack, so your party of %d would be split onto the waitlist.", free, len(party)))
+		return
+	}
+
+	names := make([]string, len(party))
+	for idx, user := range party {
+		q.addUserLocked(s, user)
+		names[idx] = q.nameLocked(user)
+	}
+	q.recordActionLocked(i.Member.User, actionJoin)
+	if err := q.refreshLocked(s); err != nil {
+		interactionLogger(i).Error("error editing message after party join", "err", err)
+	}
+
+	q.respondRolePickerLocked(s, i, tr("Joined with %s. %s", strings.Join(names[1:], ", "), q.positionTextLocked(i.Member.User.ID)))
+}
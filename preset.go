@@ -0,0 +1,213 @@
+package main
+
+import (
+	"fmt"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// defaultPreset is used when /standby doesn't name a preset, and for queues
+// opened by scheduled events.
+const defaultPreset = "5-stack"
+
+// preset bundles how a queue plays: how many players make a stack, how the
+// Split Teams and Draft buttons build teams, when a stall is alerted and
+// the embed color.
+type preset struct {
+	Name string `json:"name"`
+	// Size is how many players are needed before the stack is pinged.
+	Size int `json:"size"`
+	// TeamSize is how many players Split Teams and Draft put on each team.
+	TeamSize   int           `json:"team_size"`
+	TeamSplit  string        `json:"team_split,omitempty"`
+	AlertAfter time.Duration `json:"alert_after"`
+	// Color overrides the theme color of the queue embed when set.
+	Color int `json:"color,omitempty"`
+}
+
+// builtinPresets are the presets every guild starts with. They follow the
+// TeamSplit and AlertAfter settings, and can be replaced with
+// /standby-preset.
+func builtinPresets() map[string]preset {
+	return map[string]preset{
+		defaultPreset: {
+			Name:       defaultPreset,
+			Size:       stackSize,
+			TeamSize:   stackSize,
+			TeamSplit:  TeamSplit,
+			AlertAfter: AlertAfter,
+		},
+		"10-man inhouse": {
+			Name:       "10-man inhouse",
+			Size:       2 * stackSize,
+			TeamSize:   stackSize,
+			TeamSplit:  TeamSplit,
+			AlertAfter: AlertAfter,
+		},
+		"trio": {
+			Name:       "trio",
+			Size:       3,
+			TeamSize:   3,
+			TeamSplit:  TeamSplit,
+			AlertAfter: AlertAfter,
+		},
+	}
+}
+
+// presetLocked looks up a preset by name, falling back to defaultPreset.
+//
+// lock must be held
+func (q *queueState) presetLocked(name string) preset {
+	if p, ok := q.presets[name]; ok {
+		return p
+	}
+	if p, ok := q.presets[defaultPreset]; ok {
+		return p
+	}
+	return builtinPresets()[defaultPreset]
+}
+
+// check returns why the preset couldn't be saved with /standby-preset, or
+// "" if it could. Restored backups are held to the same limits.
+func (p preset) check() string {
+	switch {
+	case p.Name == "":
+		return tr("Preset name cannot be empty.")
+	case p.Size < 2 || p.Size > 25:
+		return tr("Preset **%s** needs a size between 2 and 25.", p.Name)
+	case p.TeamSize < 1 || p.TeamSize > max(12, p.Size):
+		return tr("Preset **%s** needs a team size between 1 and 12.", p.Name)
+	case p.AlertAfter <= 0:
+		return tr("Preset **%s** needs an alert time.", p.Name)
+	case p.Color < 0 || p.Color > 0xFFFFFF:
+		return tr("Use a hex color like #FF7518.")
+	}
+	return ""
+}
+
+// parseColor parses a hex embed color such as "#FF7518". Discord rejects
+// embeds with colors past 0xFFFFFF, so those don't parse.
+func parseColor(v string) (int, bool) {
+	c, err := strconv.ParseUint(strings.TrimPrefix(v, "#"), 16, 24)
+	if err != nil {
+		return 0, false
+	}
+	return int(c), true
+}
+
+func (q *queueState) handlePresetCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+	sub := i.ApplicationCommandData().Options[0]
+
+	q.Lock()
+	defer q.Unlock()
+
+	switch sub.Name {
+	case "set":
+		var p preset
+		for _, opt := range sub.Options {
+			switch opt.Name {
+			case "name":
+				p.Name = strings.TrimSpace(opt.StringValue())
+			case "size":
+				p.Size = int(opt.IntValue())
+			case "team_size":
+				p.TeamSize = int(opt.IntValue())
+			case "team_split":
+				p.TeamSplit = opt.StringValue()
+			case "alert_after":
+				p.AlertAfter = time.Duration(opt.IntValue()) * time.Minute
+			case "color":
+				c, ok := parseColor(opt.StringValue())
+				if !ok {
+					respondEphemeral(s, i, tr("Use a hex color like #FF7518."))
+					return
+				}
+				p.Color = c
+			}
+		}
+		if p.TeamSize == 0 {
+			p.TeamSize = p.Size
+		}
+		if p.AlertAfter == 0 {
+			p.AlertAfter = AlertAfter
+		}
+		if reason := p.check(); reason != "" {
+			respondEphemeral(s, i, reason)
+			return
+		}
+		q.presets[p.Name] = p
+		respondEphemeral(s, i, tr("Saved preset **%s**: %s", p.Name, p.describe()))
+
+	case "delete":
+		name := sub.Options[0].StringValue()
+		if _, ok := q.presets[name]; !ok {
+			respondEphemeral(s, i, tr("There is no preset named **%s**.", name))
+			return
+		}
+		delete(q.presets, name)
+		respondEphemeral(s, i, tr("Deleted preset **%s**.", name))
+
+	case "list":
+		names := make([]string, 0, len(q.presets))
+		for name := range q.presets {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+		var sb strings.Builder
+		for _, name := range names {
+			sb.WriteString(fmt.Sprintf("**%s**: %s\n", name, q.presets[name].describe()))
+		}
+		if sb.Len() == 0 {
+			respondEphemeral(s, i, tr("There are no presets."))
+			return
+		}
+		respondEphemeral(s, i, sb.String())
+	}
+}
+
+// handlePresetAutocomplete suggests preset names matching what has been
+// typed so far.
+func (q *queueState) handlePresetAutocomplete(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	typed := strings.ToLower(focusedOption(i.ApplicationCommandData().Options).StringValue())
+
+	q.Lock()
+	names := make([]string, 0, len(q.presets))
+	for name := range q.presets {
+		if strings.Contains(strings.ToLower(name), typed) {
+			names = append(names, name)
+		}
+	}
+	q.Unlock()
+	sort.Strings(names)
+
+	choices := []*discordgo.ApplicationCommandOptionChoice{}
+	for _, name := range names[:min(len(names), 25)] {
+		choices = append(choices, &discordgo.ApplicationCommandOptionChoice{Name: name, Value: name})
+	}
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionApplicationCommandAutocompleteResult,
+		Data: &discordgo.InteractionResponseData{
+			Choices: choices,
+		},
+	})
+}
+
+func (p preset) describe() string {
+	split := "random"
+	if p.TeamSplit == "rating" {
+		split = "rating"
+	}
+	desc := fmt.Sprintf("%d players, teams of %d (%s), alert after %s", p.Size, p.TeamSize, split, p.AlertAfter)
+	if p.Color != 0 {
+		desc += fmt.Sprintf(", color #%06X", p.Color)
+	}
+	return desc
+}
@@ -0,0 +1,49 @@
+package main
+
+import "testing"
+
+func TestParseColor(t *testing.T) {
+	for _, tt := range []struct {
+		value string
+		want  int
+		ok    bool
+	}{
+		{"#FF7518", 0xFF7518, true},
+		{"ff7518", 0xFF7518, true},
+		{"#FFFFFF", 0xFFFFFF, true},
+		{"#1000000", 0, false},
+		{"-1", 0, false},
+		{"orange", 0, false},
+	} {
+		if got, ok := parseColor(tt.value); got != tt.want || ok != tt.ok {
+			t.Errorf("parseColor(%q) = %#x, %v, want %#x, %v", tt.value, got, ok, tt.want, tt.ok)
+		}
+	}
+}
+
+func TestPresetCheck(t *testing.T) {
+	for name, p := range builtinPresets() {
+		if reason := p.check(); reason != "" {
+			t.Errorf("builtin preset %q: %s", name, reason)
+		}
+	}
+
+	valid := builtinPresets()[defaultPreset]
+	for _, tt := range []struct {
+		name string
+		edit func(p *preset)
+	}{
+		{"no name", func(p *preset) { p.Name = "" }},
+		{"no size", func(p *preset) { p.Size = 0 }},
+		{"negative size", func(p *preset) { p.Size = -1 }},
+		{"no team size", func(p *preset) { p.TeamSize = 0 }},
+		{"no alert", func(p *preset) { p.AlertAfter = 0 }},
+		{"color too large", func(p *preset) { p.Color = 0x1000000 }},
+	} {
+		p := valid
+		tt.edit(&p)
+		if p.check() == "" {
+			t.Errorf("%s: check() passed %+v", tt.name, p)
+		}
+	}
+}
@@ -0,0 +1,305 @@
+package main
+
+import (
+	"fmt"
+	"log/slog"
+	"math/rand"
+	"os"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// promotionPolicy decides which waitlisted user takes a slot that opens up
+// in the stack.
+type promotionPolicy string
+
+const (
+	// promoteFIFO promotes the user who has waited longest.
+	promoteFIFO promotionPolicy = "fifo"
+	// promotePriority promotes the first waitlisted user with
+	// PriorityRoleID, falling back to FIFO.
+	promotePriority promotionPolicy = "priority"
+	// promoteLeastGames promotes whoever has been in the fewest ready stacks
+	// tonight, breaking ties by wait time.
+	promoteLeastGames promotionPolicy = "least-games"
+	// promoteRandom promotes a random waitlisted user.
+	promoteRandom promotionPolicy = "random"
+)
+
+// gamesWindow is how far back "tonight" reaches for promoteLeastGames.
+const gamesWindow = 12 * time.Hour
+
+var (
+	PromotionPolicy = promotionPolicyEnv("STANDBY_PROMOTION_POLICY")
+	PriorityRoleID  = os.Getenv("STANDBY_PRIORITY_ROLE_ID")
+	// PromotionTimeout is how long a promoted waitlister has to accept their
+	// spot before it goes to the next person. 0 promotes without asking.
+	PromotionTimeout = durationEnv("STANDBY_PROMOTION_TIMEOUT", 2*time.Minute)
+)
+
+// promotionPolicyEnv reads a promotionPolicy from the environment, falling
+// back to promoteFIFO when unset.
+func promotionPolicyEnv(key string) promotionPolicy {
+	switch p := promotionPolicy(os.Getenv(key)); p {
+	case "":
+		return promoteFIFO
+	case promoteFIFO, promotePriority, promoteLeastGames, promoteRandom:
+		return p
+	default:
+		panic(fmt.Sprintf("invalid %s: unknown policy %q", key, p))
+	}
+}
+
+// promotionOffer is a stack slot offered to a promoted waitlister.
+type promotionOffer struct {
+	msgID string
+	timer *time.Timer
+}
+
+// promoteLocked moves the waitlisted user chosen by PromotionPolicy into the
+// last slot of the stack and asks them to accept it. It must be called after
+// a user within the stack was removed, at which point the longest-waiting
+// user has already shifted into that slot.
+//
+// lock must be held
+func (q *queueState) promoteLocked(s *discordgo.Session) {
+	slot := q.preset.Size - 1
+	if len(q.users) < slot+1 {
+		return
+	}
+	waitlist := q.users[slot:]
+
+	pick := slot
+	switch PromotionPolicy {
+	case promotePriority:
+		for j, user := range waitlist {
+			if hasRole(s, user.ID, PriorityRoleID) {
+				pick = slot + j
+				break
+			}
+		}
+	case promoteLeastGames:
+		fewest := -1
+		for j, user := range waitlist {
+			if n := q.gamesTonightLocked(user.ID); fewest < 0 || n < fewest {
+				fewest = n
+				pick = slot + j
+			}
+		}
+	case promoteRandom:
+		pick = slot + rand.Intn(len(waitlist))
+	}
+	if pick != slot {
+		user := q.users[pick]
+		copy(q.users[slot+1:pick+1], q.users[slot:pick])
+		q.users[slot] = user
+	}
+	q.offerPromotionLocked(s, q.users[slot])
+}
+
+// offerPromotionLocked asks a user just promoted into the stack to confirm
+// they're still around. The ready ping is held back until they accept;
+// declining or not answering within PromotionTimeout drops them from the
+// queue, which promotes the next person.
+//
+// lock must be held
+func (q *queueState) offerPromotionLocked(s *discordgo.Session, user *discordgo.User) {
+	if PromotionTimeout <= 0 || isSimulated(user.ID) {
+		return
+	}
+	q.cancelOfferLocked(s, user.ID)
+
+	deadline := time.Now().Add(PromotionTimeout)
+	m, err := sendMessage(s, q.channelLocked(), &discordgo.MessageSend{
+		Content: tr("%s, a spot opened up in the stack! Accept it <t:%d:R> or it goes to the next person.", mention(user), deadline.Unix()),
+		Components: []discordgo.MessageComponent{
+			discordgo.ActionsRow{
+				Components: []discordgo.MessageComponent{
+					discordgo.Button{
+						Label:    tr("Accept"),
+						Style:    discordgo.SuccessButton,
+						CustomID: "accept_promotion",
+					},
+					discordgo.Button{
+						Label:    tr("Decline"),
+						Style:    discordgo.DangerButton,
+						CustomID: "decline_promotion",
+					},
+				},
+			},
+		},
+	})
+	if err != nil {
+		// Without a prompt they can't answer, so they keep the spot.
+		slog.Error("error sending promotion offer", "err", err, "user", user.ID)
+		return
+	}
+
+	userID := user.ID
+	offer := &promotionOffer{msgID: m.ID}
+	offer.timer = time.AfterFunc(PromotionTimeout, func() {
+		q.Lock()
+		defer q.Unlock()
+
+		if q.offers[userID] != offer {
+			return
+		}
+		if !q.inStackLocked(userID) {
+			q.cancelOfferLocked(s, userID)
+			return
+		}
+		q.takeOfferLocked(userID)
+		content := tr("%s didn't answer in time, so the spot went to the next person.", mention(user))
+		if _, err := editMessage(s, &discordgo.MessageEdit{
+			ID:         offer.msgID,
+			Channel:    q.channelLocked(),
+			Content:    &content,
+			Components: []discordgo.MessageComponent{},
+		}); err != nil {
+			slog.Error("error editing expired promotion offer", "err", err, "message", offer.msgID)
+		}
+		if removed := q.removeUserLocked(s, userID); removed != nil {
+			q.recordActionLocked(removed, actionLeave)
+			q.scheduleRefreshLocked(s)
+		}
+	})
+	q.offers[userID] = offer
+}
+
+// handlePromotionLocked answers the Accept and Decline buttons on a
+// promotion offer.
+//
+// lock must be held
+func (q *queueState) handlePromotionLocked(s *discordgo.Session, i *discordgo.InteractionCreate, accept bool) {
+	userID := i.Member.User.ID
+	if offer, ok := q.offers[userID]; !ok || offer.msgID != i.Message.ID {
+		respondEphemeral(s, i, tr("This offer isn't for you or has expired."))
+		return
+	}
+	if !q.inStackLocked(userID) {
+		q.cancelOfferLocked(s, userID)
+		respondEphemeral(s, i, tr("That spot has already been filled."))
+		return
+	}
+	q.takeOfferLocked(userID)
+
+	content := tr("%s took the spot.", mention(i.Member.User))
+	if !accept {
+		content = tr("%s passed on the spot.", mention(i.Member.User))
+		if user := q.removeUserLocked(s, userID); user != nil {
+			q.recordActionLocked(user, actionLeave)
+		}
+	}
+	q.scheduleRefreshLocked(s)
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseUpdateMessage,
+		Data: &discordgo.InteractionResponseData{
+			Content:         content,
+			Components:      []discordgo.MessageComponent{},
+			AllowedMentions: &discordgo.MessageAllowedMentions{},
+		},
+	})
+	if accept && len(GameRoles) > 0 {
+		q.followupJoinedLocked(s, i)
+	}
+}
+
+// takeOfferLocked stops the user's pending offer and forgets it, returning
+// it, or nil if there was none.
+//
+// lock must be held
+func (q *queueState) takeOfferLocked(userID string) *promotionOffer {
+	offer, ok := q.offers[userID]
+	if !ok {
+		return nil
+	}
+	offer.timer.Stop()
+	delete(q.offers, userID)
+	return offer
+}
+
+// dropStaleOffersLocked withdraws the offers of users who were moved out of
+// the stack, e.g. by /standby-swap, so they neither hold back the ready ping
+// nor get dropped from the waitlist when the offer times out.
+//
+// lock must be held
+func (q *queueState) dropStaleOffersLocked(s *discordgo.Session) {
+	for userID := range q.offers {
+		if !q.inStackLocked(userID) {
+			q.cancelOfferLocked(s, userID)
+		}
+	}
+}
+
+// inStackLocked reports whether the user holds one of the stack's slots.
+//
+// lock must be held
+func (q *queueState) inStackLocked(userID string) bool {
+	pos := q.positionLocked(userID)
+	return pos > 0 && pos <= q.preset.Size
+}
+
+// cancelOfferLocked withdraws the user's pending offer, if any, deleting its
+// prompt.
+//
+// lock must be held
+func (q *queueState) cancelOfferLocked(s *discordgo.Session, userID string) {
+	offer := q.takeOfferLocked(userID)
+	if offer == nil {
+		return
+	}
+	if err := deleteMessage(s, q.channelLocked(), offer.msgID); err != nil {
+		slog.Error("error deleting promotion offer", "err", err, "message", offer.msgID)
+	}
+}
+
+// recordGamesLocked notes that the users in the stack got a game, for
+// promoteLeastGames.
+//
+// lock must be held
+func (q *queueState) recordGamesLocked() {
+	now := time.Now()
+	for idx, user := range q.users {
+		if idx == q.preset.Size {
+			break
+		}
+		q.games[user.ID] = append(q.games[user.ID], now)
+	}
+}
+
+// lock must be held
+func (q *queueState) gamesTonightLocked(userID string) int {
+	games := q.games[userID]
+	for len(games) > 0 && time.Since(games[0]) > gamesWindow {
+		games = games[1:]
+	}
+	if len(games) == 0 {
+		delete(q.games, userID)
+	} else {
+		q.games[userID] = games
+	}
+	return len(games)
+}
+
+// hasRole reports whether a guild member has the given role, preferring the
+// state cache over an API call. It is a variable so tests can stub it.
+var hasRole = func(s *discordgo.Session, userID, roleID string) bool {
+	if roleID == "" || isSimulated(userID) {
+		return false
+	}
+	m, err := s.State.Member(GuildID, userID)
+	if err != nil {
+		m, err = s.GuildMember(GuildID, userID)
+		if err != nil {
+			slog.Error("error fetching member", "err", err, "user", userID)
+			return false
+		}
+	}
+	for _, r := range m.Roles {
+		if r == roleID {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,192 @@
+package main
+
+import (
+	"fmt"
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// promotionQueue returns a queue of three with users a through e, as it is
+// right after a stack user left: c has shifted into the last slot and d and
+// e are waitlisted.
+func promotionQueue() *queueState {
+	q := &queueState{
+		preset: preset{Name: "trio", Size: 3},
+		games:  map[string][]time.Time{},
+		offers: map[string]*promotionOffer{},
+	}
+	for _, name := range []string{"a", "b", "c", "d", "e"} {
+		q.users = append(q.users, &discordgo.User{ID: name, Username: name})
+	}
+	return q
+}
+
+func userIDs(users []*discordgo.User) []string {
+	ids := make([]string, len(users))
+	for idx, user := range users {
+		ids[idx] = user.ID
+	}
+	return ids
+}
+
+// withPromotion sets the promotion settings for the duration of a test,
+// without offers so nothing is sent to Discord.
+func withPromotion(t *testing.T, policy promotionPolicy, roles map[string]bool) {
+	t.Helper()
+	oldPolicy, oldTimeout, oldHasRole := PromotionPolicy, PromotionTimeout, hasRole
+	t.Cleanup(func() {
+		PromotionPolicy, PromotionTimeout, hasRole = oldPolicy, oldTimeout, oldHasRole
+	})
+	PromotionPolicy = policy
+	PromotionTimeout = 0
+	hasRole = func(_ *discordgo.Session, userID, _ string) bool {
+		return roles[userID]
+	}
+}
+
+func TestPromoteLocked(t *testing.T) {
+	tonight := time.Now().Add(-time.Hour)
+	lastWeek := time.Now().Add(-7 * 24 * time.Hour)
+
+	tests := []struct {
+		name   string
+		policy promotionPolicy
+		roles  map[string]bool
+		games  map[string][]time.Time
+		want   []string
+	}{
+		{
+			name:   "fifo keeps the longest waiting",
+			policy: promoteFIFO,
+			want:   []string{"a", "b", "c", "d", "e"},
+		},
+		{
+			name:   "priority promotes the first with the role",
+			policy: promotePriority,
+			roles:  map[string]bool{"e": true},
+			want:   []string{"a", "b", "e", "c", "d"},
+		},
+		{
+			name:   "priority ignores the role in the stack",
+			policy: promotePriority,
+			roles:  map[string]bool{"a": true, "d": true},
+			want:   []string{"a", "b", "d", "c", "e"},
+		},
+		{
+			name:   "priority falls back to fifo",
+			policy: promotePriority,
+			want:   []string{"a", "b", "c", "d", "e"},
+		},
+		{
+			name:   "least-games promotes the fewest games tonight",
+			policy: promoteLeastGames,
+			games: map[string][]time.Time{
+				"c": {tonight, tonight},
+				"e": {tonight},
+			},
+			want: []string{"a", "b", "d", "c", "e"},
+		},
+		{
+			name:   "least-games ignores games before tonight",
+			policy: promoteLeastGames,
+			games: map[string][]time.Time{
+				"c": {tonight},
+				"d": {tonight},
+				"e": {lastWeek, lastWeek},
+			},
+			want: []string{"a", "b", "e", "c", "d"},
+		},
+		{
+			name:   "least-games breaks ties by wait time",
+			policy: promoteLeastGames,
+			games: map[string][]time.Time{
+				"c": {tonight},
+			},
+			want: []string{"a", "b", "d", "c", "e"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			withPromotion(t, tt.policy, tt.roles)
+			q := promotionQueue()
+			for userID, games := range tt.games {
+				q.games[userID] = games
+			}
+
+			q.promoteLocked(nil)
+			if got := userIDs(q.users); !slices.Equal(got, tt.want) {
+				t.Errorf("users = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestPromoteLockedRandom(t *testing.T) {
+	withPromotion(t, promoteRandom, nil)
+
+	picked := map[string]bool{}
+	for n := 0; n < 200; n++ {
+		q := promotionQueue()
+		q.promoteLocked(nil)
+
+		got := userIDs(q.users)
+		if !slices.Equal(got[:2], []string{"a", "b"}) {
+			t.Fatalf("stack changed: users = %q", got)
+		}
+		if !slices.Contains([]string{"c", "d", "e"}, got[2]) {
+			t.Fatalf("promoted %q, who wasn't waitlisted", got[2])
+		}
+		rest := slices.DeleteFunc([]string{"c", "d", "e"}, func(id string) bool { return id == got[2] })
+		if !slices.Equal(got[3:], rest) {
+			t.Fatalf("waitlist = %q, want %q in order", got[3:], rest)
+		}
+		picked[got[2]] = true
+	}
+	if len(picked) != 3 {
+		t.Errorf("only ever promoted %v", picked)
+	}
+}
+
+func TestPromoteLockedShortQueue(t *testing.T) {
+	withPromotion(t, promoteRandom, nil)
+
+	q := promotionQueue()
+	q.users = q.users[:2]
+	q.promoteLocked(nil)
+	if got := userIDs(q.users); !slices.Equal(got, []string{"a", "b"}) {
+		t.Errorf("users = %q, want unchanged", got)
+	}
+}
+
+func TestPromotionPolicyEnv(t *testing.T) {
+	for _, tt := range []struct {
+		value string
+		want  promotionPolicy
+	}{
+		{"", promoteFIFO},
+		{"fifo", promoteFIFO},
+		{"priority", promotePriority},
+		{"least-games", promoteLeastGames},
+		{"random", promoteRandom},
+	} {
+		t.Run(fmt.Sprintf("%q", tt.value), func(t *testing.T) {
+			t.Setenv("STANDBY_TEST_POLICY", tt.value)
+			if got := promotionPolicyEnv("STANDBY_TEST_POLICY"); got != tt.want {
+				t.Errorf("promotionPolicyEnv() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+
+	t.Run("unknown", func(t *testing.T) {
+		t.Setenv("STANDBY_TEST_POLICY", "lifo")
+		defer func() {
+			if recover() == nil {
+				t.Error("promotionPolicyEnv() didn't panic on an unknown policy")
+			}
+		}()
+		promotionPolicyEnv("STANDBY_TEST_POLICY")
+	})
+}
@@ -0,0 +1,65 @@
+package main
+
+import (
+	"math"
+	"sync"
+	"time"
+)
+
+var (
+	// CommandBurst is how many times a user may run the same command in a
+	// row before being rate limited. 0 disables the limit.
+	CommandBurst = intEnv("STANDBY_COMMAND_BURST", 5)
+	// CommandWindow is how long it takes for a user's full burst of a
+	// command to refill.
+	CommandWindow = durationEnv("STANDBY_COMMAND_WINDOW", time.Minute)
+)
+
+var commandLimiter = newRateLimiter(CommandBurst, CommandWindow)
+
+// rateLimiter is a token bucket per key, holding up to burst tokens and
+// refilling all of them over window.
+type rateLimiter struct {
+	sync.Mutex
+
+	burst   float64
+	window  time.Duration
+	buckets map[string]*bucket
+}
+
+type bucket struct {
+	tokens float64
+	last   time.Time
+}
+
+func newRateLimiter(burst int, window time.Duration) *rateLimiter {
+	return &rateLimiter{
+		burst:   float64(burst),
+		window:  window,
+		buckets: map[string]*bucket{},
+	}
+}
+
+// take spends a token for key, returning how long to wait if none are left.
+func (r *rateLimiter) take(key string) (time.Duration, bool) {
+	if r.burst <= 0 {
+		return 0, true
+	}
+	r.Lock()
+	defer r.Unlock()
+
+	now := time.Now()
+	perToken := r.window.Seconds() / r.burst
+	b, ok := r.buckets[key]
+	if !ok {
+		b = &bucket{tokens: r.burst, last: now}
+		r.buckets[key] = b
+	}
+	b.tokens = math.Min(r.burst, b.tokens+now.Sub(b.last).Seconds()/perToken)
+	b.last = now
+	if b.tokens < 1 {
+		return time.Duration((1 - b.tokens) * perToken * float64(time.Second)), false
+	}
+	b.tokens--
+	return 0, true
+}
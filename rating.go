@@ -0,0 +1,105 @@
+package main
+
+import (
+	"fmt"
+	"math"
+	"os"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+const (
+	initialRating = 1000.0
+	// ratingK is the Elo K-factor: the most a single result can move a
+	// rating.
+	ratingK = 32.0
+)
+
+// ShowRatings shows each user's rating next to their name in the queue.
+var ShowRatings = os.Getenv("STANDBY_SHOW_RATINGS") == "true"
+
+// lock must be held
+func (q *queueState) ratingLocked(userID string) float64 {
+	if r, ok := q.ratings[userID]; ok {
+		return r
+	}
+	return initialRating
+}
+
+// applyRatingsLocked updates the ratings of a stack after a result. Opponents
+// are unknown, so the stack is rated against an average team at
+// initialRating: strong stacks gain little from wins and lose more from
+// losses.
+//
+// lock must be held
+func (q *queueState) applyRatingsLocked(players []string, win bool) {
+	var total float64
+	for _, id := range players {
+		total += q.ratingLocked(id)
+	}
+	avg := total / float64(len(players))
+	expected := 1 / (1 + math.Pow(10, (initialRating-avg)/400))
+
+	score := 0.0
+	if win {
+		score = 1
+	}
+	for _, id := range players {
+		q.ratings[id] = q.ratingLocked(id) + ratingK*(score-expected)
+	}
+}
+
+// lock must be held
+func (q *queueState) recordLocked(userID string) (wins, losses int) {
+	for _, r := range q.results {
+		for _, id := range r.Players {
+			if id != userID {
+				continue
+			}
+			if r.Win {
+				wins++
+			} else {
+				losses++
+			}
+		}
+	}
+	return wins, losses
+}
+
+func (q *queueState) handleStatsCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	user := i.Member.User
+	if opts := i.ApplicationCommandData().Options[0].Options; len(opts) > 0 {
+		user = opts[0].UserValue(s)
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	wins, losses := q.recordLocked(user.ID)
+	st := q.statsLocked(user.ID)
+	avgWait := "-"
+	if st.Filled > 0 {
+		avgWait = st.averageWait().String()
+	}
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{
+			Embeds: []*discordgo.MessageEmbed{
+				{
+					Type:  discordgo.EmbedTypeRich,
+					Title: tr("Stats for %s", q.nameLocked(user)),
+					Color: 0x0099FF,
+					Fields: []*discordgo.MessageEmbedField{
+						{Name: tr("Rating"), Value: fmt.Sprintf("%.0f", q.ratingLocked(user.ID)), Inline: true},
+						{Name: tr("Record"), Value: tr("%dW %dL", wins, losses), Inline: true},
+						{Name: tr("Queued"), Value: fmt.Sprintf("%d", st.Queued), Inline: true},
+						{Name: tr("Games"), Value: fmt.Sprintf("%d", st.Filled), Inline: true},
+						{Name: tr("No-shows"), Value: fmt.Sprintf("%d", st.NoShows), Inline: true},
+						{Name: tr("Avg. wait"), Value: avgWait, Inline: true},
+					},
+				},
+			},
+			Flags: discordgo.MessageFlagsEphemeral,
+		},
+	})
+}
@@ -0,0 +1,37 @@
+package main
+
+import (
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+var (
+	// MinAccountAge is how old a Discord account must be to join the queue.
+	MinAccountAge = durationEnv("STANDBY_MIN_ACCOUNT_AGE", 0)
+	// MinMemberAge is how long a user must have been in the server to join
+	// the queue.
+	MinMemberAge = durationEnv("STANDBY_MIN_MEMBER_AGE", 0)
+)
+
+// joinBlocked returns why a user may not join the queue yet, or "" if they
+// meet the membership requirements.
+func joinBlocked(s *discordgo.Session, user *discordgo.User) string {
+	if isSimulated(user.ID) || (MinAccountAge == 0 && MinMemberAge == 0) {
+		return ""
+	}
+
+	if created, err := discordgo.SnowflakeTimestamp(user.ID); err == nil && time.Since(created) < MinAccountAge {
+		return tr("%s's account is too new to queue. They can join <t:%d:R>.", user.Username, created.Add(MinAccountAge).Unix())
+	}
+	if MinMemberAge > 0 {
+		m, err := s.State.Member(GuildID, user.ID)
+		if err != nil {
+			m, err = s.GuildMember(GuildID, user.ID)
+		}
+		if err == nil && time.Since(m.JoinedAt) < MinMemberAge {
+			return tr("%s joined the server too recently to queue. They can join <t:%d:R>.", user.Username, m.JoinedAt.Add(MinMemberAge).Unix())
+		}
+	}
+	return ""
+}
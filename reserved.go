@@ -0,0 +1,147 @@
+package main
+
+import (
+	"log/slog"
+	"os"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+var (
+	// ReservedRoleID is the role that ReservedSlots stack slots are held
+	// for. Reservations are disabled when unset.
+	ReservedRoleID = os.Getenv("STANDBY_RESERVED_ROLE_ID")
+	// ReservedSlots is how many stack slots are held for ReservedRoleID.
+	ReservedSlots = intEnv("STANDBY_RESERVED_SLOTS", 0)
+	// ReservedFor is how long after a queue opens its reserved slots are
+	// released to everyone.
+	ReservedFor = durationEnv("STANDBY_RESERVED_FOR", 15*time.Minute)
+)
+
+// reservationActiveLocked reports whether the current queue still holds
+// slots for ReservedRoleID.
+//
+// lock must be held
+func (q *queueState) reservationActiveLocked() bool {
+	return ReservedRoleID != "" && ReservedSlots > 0 && time.Since(q.startTime) < ReservedFor
+}
+
+// reservedSlotsLocked is how many of the current preset's slots are
+// reserved. Presets smaller than ReservedSlots are reserved in full.
+//
+// lock must be held
+func (q *queueState) reservedSlotsLocked() int {
+	return min(ReservedSlots, q.preset.Size)
+}
+
+// tagRegularLocked remembers whether the user holds ReservedRoleID, so
+// rendering and readiness checks don't need to look roles up.
+//
+// lock must be held
+func (q *queueState) tagRegularLocked(s *discordgo.Session, user *discordgo.User) {
+	if ReservedRoleID != "" && hasRole(s, user.ID, ReservedRoleID) {
+		q.regulars[user.ID] = true
+	}
+}
+
+// placeReservedLocked moves a just-added user with ReservedRoleID ahead of
+// the users occupying reserved slots without the role, pushing them towards
+// the waitlist.
+//
+// lock must be held
+func (q *queueState) placeReservedLocked(s *discordgo.Session) {
+	last := len(q.users) - 1
+	user := q.users[last]
+	q.tagRegularLocked(s, user)
+	if !q.regulars[user.ID] || !q.reservationActiveLocked() {
+		return
+	}
+
+	reserved := q.reservedSlotsLocked()
+	unreserved := q.preset.Size - reserved
+	var others, regulars int
+	for idx, queued := range q.users[:last] {
+		if q.regulars[queued.ID] {
+			regulars++
+			continue
+		}
+		others++
+		if others <= unreserved {
+			continue
+		}
+		// queued is sitting in a reserved slot.
+		if regulars >= reserved {
+			return
+		}
+		copy(q.users[idx+1:], q.users[idx:last])
+		q.users[idx] = user
+		return
+	}
+}
+
+// reservationHeldLocked reports whether users without ReservedRoleID are
+// filling slots still reserved for it, in which case the stack isn't ready.
+//
+// lock must be held
+func (q *queueState) reservationHeldLocked() bool {
+	if !q.reservationActiveLocked() {
+		return false
+	}
+	stack := q.users[:min(len(q.users), q.preset.Size)]
+	var others int
+	for _, user := range stack {
+		if !q.regulars[user.ID] {
+			others++
+		}
+	}
+	return others > q.preset.Size-q.reservedSlotsLocked()
+}
+
+// reservationTextLocked describes the active reservation for the queue
+// embed, or returns "" if there is none.
+//
+// lock must be held
+func (q *queueState) reservationTextLocked() string {
+	if !q.reservationActiveLocked() {
+		return ""
+	}
+	return tr("%d slots reserved for <@&%s> until <t:%d:t>\n",
+		q.reservedSlotsLocked(), ReservedRoleID, q.startTime.Add(ReservedFor).Unix())
+}
+
+// armReservationLocked schedules a refresh for when the current queue's
+// reserved slots are released, so a held stack can become ready.
+//
+// lock must be held
+func (q *queueState) armReservationLocked(s *discordgo.Session) {
+	q.stopReservationLocked()
+	if ReservedRoleID == "" || ReservedSlots <= 0 {
+		return
+	}
+	started := q.startTime
+	remaining := ReservedFor - time.Since(started)
+	if remaining <= 0 {
+		return
+	}
+	q.reserveTimer = time.AfterFunc(remaining, func() {
+		q.Lock()
+		defer q.Unlock()
+
+		if q.currentMsgID == "" || !q.startTime.Equal(started) {
+			return
+		}
+		q.reserveTimer = nil
+		if err := q.refreshLocked(s); err != nil {
+			slog.Error("error editing message releasing reserved slots", "err", err)
+		}
+	})
+}
+
+// lock must be held
+func (q *queueState) stopReservationLocked() {
+	if q.reserveTimer != nil {
+		q.reserveTimer.Stop()
+		q.reserveTimer = nil
+	}
+}
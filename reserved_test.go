@@ -0,0 +1,43 @@
+package main
+
+import (
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+func TestReservationSmallPreset(t *testing.T) {
+	oldRole, oldSlots, oldFor, oldHasRole := ReservedRoleID, ReservedSlots, ReservedFor, hasRole
+	t.Cleanup(func() {
+		ReservedRoleID, ReservedSlots, ReservedFor, hasRole = oldRole, oldSlots, oldFor, oldHasRole
+	})
+	ReservedRoleID = "regular"
+	ReservedSlots = 3
+	ReservedFor = time.Hour
+	hasRole = func(_ *discordgo.Session, userID, _ string) bool {
+		return userID == alice.ID || userID == bob.ID
+	}
+
+	q := &queueState{
+		preset:    preset{Name: "duo", Size: 2},
+		regulars:  map[string]bool{alice.ID: true, bob.ID: true},
+		startTime: time.Now(),
+	}
+
+	q.users = []*discordgo.User{alice, bob}
+	if q.reservationHeldLocked() {
+		t.Error("stack of regulars held back")
+	}
+	q.users = []*discordgo.User{alice, carol}
+	if !q.reservationHeldLocked() {
+		t.Error("stack with a non-regular in a reserved slot not held back")
+	}
+
+	q.users = []*discordgo.User{carol, alice}
+	q.placeReservedLocked(nil)
+	if got := userIDs(q.users); !slices.Equal(got, []string{alice.ID, carol.ID}) {
+		t.Errorf("users = %q, want the regular ahead", got)
+	}
+}
@@ -0,0 +1,72 @@
+package main
+
+import (
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// matchResult is the outcome of a game played by a ready stack.
+type matchResult struct {
+	Time    time.Time `json:"time"`
+	Players []string  `json:"players"`
+	Win     bool      `json:"win"`
+	Score   string    `json:"score,omitempty"`
+}
+
+func (q *queueState) handleResultCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	var outcome, score string
+	for _, opt := range i.ApplicationCommandData().Options {
+		switch opt.Name {
+		case "outcome":
+			outcome = opt.StringValue()
+		case "score":
+			score = strings.TrimSpace(opt.StringValue())
+		}
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if len(q.lastStack) == 0 {
+		respondEphemeral(s, i, tr("There is no finished stack to record a result for."))
+		return
+	}
+
+	var inStack bool
+	players := make([]string, len(q.lastStack))
+	mentions := make([]string, len(q.lastStack))
+	for idx, user := range q.lastStack {
+		players[idx] = user.ID
+		mentions[idx] = mention(user)
+		if user.ID == i.Member.User.ID {
+			inStack = true
+		}
+	}
+	if !inStack && !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only players from the last stack or admins can record its result."))
+		return
+	}
+
+	q.results = append(q.results, matchResult{
+		Time:    time.Now(),
+		Players: players,
+		Win:     outcome == "win",
+		Score:   score,
+	})
+	q.applyRatingsLocked(players, outcome == "win")
+	q.lastStack = nil
+
+	content := tr("Recorded a **%s** for %s.", outcome, strings.Join(mentions, ", "))
+	if score != "" {
+		content = tr("Recorded a **%s** (%s) for %s.", outcome, score, strings.Join(mentions, ", "))
+	}
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{
+			Content:         content,
+			AllowedMentions: &discordgo.MessageAllowedMentions{},
+		},
+	})
+}
@@ -0,0 +1,23 @@
+package main
+
+import (
+	"log/slog"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// resync re-renders the queue message and notification after the gateway
+// reconnects, since interactions sent while it was down may never arrive
+// and the message can disagree with the queue state.
+func (q *queueState) resync(s *discordgo.Session) {
+	q.Lock()
+	defer q.Unlock()
+
+	if q.currentMsgID == "" {
+		return
+	}
+	slog.Info("resyncing queue message after reconnect", "message", q.currentMsgID)
+	if err := q.refreshLocked(s); err != nil {
+		slog.Error("error resyncing queue message", "err", err, "message", q.currentMsgID)
+	}
+}
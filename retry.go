@@ -0,0 +1,117 @@
+package main
+
+import (
+	"errors"
+	"log/slog"
+	"net"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+const (
+	// retryAttempts is how many times a transiently failing Discord call is
+	// tried in total.
+	retryAttempts = 3
+	// retryBackoff is the wait before the first retry, doubling after each.
+	retryBackoff = 250 * time.Millisecond
+)
+
+// latestEdits holds the most recent edit of each message that is still in
+// flight or being retried, so a retry never overwrites a newer edit.
+var latestEdits = struct {
+	sync.Mutex
+	m map[string]*discordgo.MessageEdit
+}{m: map[string]*discordgo.MessageEdit{}}
+
+// retryLater retries a failed Discord call in the background with
+// exponential backoff, so callers holding the queue lock never wait on it.
+// op returns false if the call is no longer wanted. Permanent failures are
+// logged.
+func retryLater(what string, op func() (bool, error)) {
+	go func() {
+		backoff := retryBackoff
+		for attempt := 2; attempt <= retryAttempts; attempt++ {
+			time.Sleep(backoff)
+			backoff *= 2
+			wanted, err := op()
+			if !wanted || err == nil {
+				return
+			}
+			if attempt == retryAttempts || !transient(err) {
+				slog.Error("giving up on discord call", "op", what, "err", err, "attempts", attempt)
+				return
+			}
+			slog.Warn("retrying discord call", "op", what, "err", err, "attempt", attempt)
+		}
+	}()
+}
+
+// sendMessage posts a message. Sends are never retried: a request that timed
+// out may still have been posted, and retrying would post it twice.
+func sendMessage(s *discordgo.Session, channelID string, msg *discordgo.MessageSend) (*discordgo.Message, error) {
+	return s.ChannelMessageSendComplex(channelID, msg)
+}
+
+// editMessage edits a message, retrying server errors and network failures
+// in the background unless the message is edited again in the meantime. The
+// first attempt's result is returned; callers that re-render, such as the
+// queue refresh, don't depend on the retry.
+func editMessage(s *discordgo.Session, edit *discordgo.MessageEdit) (*discordgo.Message, error) {
+	latestEdits.Lock()
+	latestEdits.m[edit.ID] = edit
+	latestEdits.Unlock()
+
+	m, err := s.ChannelMessageEditComplex(edit)
+	if err == nil || !transient(err) {
+		forgetEdit(edit)
+		return m, err
+	}
+	retryLater("edit", func() (bool, error) {
+		latestEdits.Lock()
+		current := latestEdits.m[edit.ID] == edit
+		latestEdits.Unlock()
+		if !current {
+			return false, nil
+		}
+		_, err := s.ChannelMessageEditComplex(edit)
+		if err == nil || !transient(err) {
+			forgetEdit(edit)
+		}
+		return true, err
+	})
+	return m, err
+}
+
+// forgetEdit drops edit from latestEdits unless a newer edit replaced it.
+func forgetEdit(edit *discordgo.MessageEdit) {
+	latestEdits.Lock()
+	defer latestEdits.Unlock()
+	if latestEdits.m[edit.ID] == edit {
+		delete(latestEdits.m, edit.ID)
+	}
+}
+
+// deleteMessage deletes a message, retrying server errors and network
+// failures in the background. Only permanent failures are returned.
+func deleteMessage(s *discordgo.Session, channelID, msgID string) error {
+	err := s.ChannelMessageDelete(channelID, msgID)
+	if err == nil || !transient(err) {
+		return err
+	}
+	retryLater("delete", func() (bool, error) {
+		return true, s.ChannelMessageDelete(channelID, msgID)
+	})
+	return nil
+}
+
+func transient(err error) bool {
+	var restErr *discordgo.RESTError
+	if errors.As(err, &restErr) {
+		return restErr.Response != nil && restErr.Response.StatusCode >= http.StatusInternalServerError
+	}
+	var netErr net.Error
+	return errors.As(err, &netErr)
+}
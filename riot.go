@@ -0,0 +1,235 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"log/slog"
+	"net/http"
+	"net/url"
+	"os"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+var (
+	// RiotAPIKey enables /link and League of Legends ranks next to queued
+	// users. Rank lookups are disabled when it is unset.
+	RiotAPIKey = os.Getenv("STANDBY_RIOT_API_KEY")
+	// RiotRegion routes Riot ID lookups, e.g. "americas" or "europe".
+	RiotRegion = stringEnv("STANDBY_RIOT_REGION", "americas")
+	// RiotPlatform routes rank lookups, e.g. "na1" or "euw1".
+	RiotPlatform = stringEnv("STANDBY_RIOT_PLATFORM", "na1")
+)
+
+const (
+	// rankTTL is how long a fetched rank is used before it is looked up
+	// again.
+	rankTTL = 30 * time.Minute
+	// rankKeep is how long a rank is cached at all.
+	rankKeep = 12 * time.Hour
+)
+
+// riotLink is a Riot account linked with /link.
+type riotLink struct {
+	RiotID string `json:"riot_id"`
+	PUUID  string `json:"puuid"`
+}
+
+type cachedRank struct {
+	rank    string
+	fetched time.Time
+}
+
+// riotClient calls the Riot API and caches ranks by PUUID.
+type riotClient struct {
+	sync.Mutex
+
+	http     *http.Client
+	ranks    map[string]cachedRank
+	inflight map[string]bool
+}
+
+var riot = &riotClient{
+	http:     &http.Client{Timeout: 10 * time.Second},
+	ranks:    map[string]cachedRank{},
+	inflight: map[string]bool{},
+}
+
+func (c *riotClient) get(host, path string, v any) error {
+	req, err := http.NewRequest(http.MethodGet, "https://"+host+".api.riotgames.com"+path, nil)
+	if err != nil {
+		return err
+	}
+	req.Header.Set("X-Riot-Token", RiotAPIKey)
+	resp, err := c.http.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return fmt.Errorf("riot api: unexpected status %s", resp.Status)
+	}
+	return json.NewDecoder(resp.Body).Decode(v)
+}
+
+// account resolves a Riot ID such as "Name#TAG" to its PUUID.
+func (c *riotClient) account(riotID string) (string, error) {
+	name, tag, ok := strings.Cut(riotID, "#")
+	if !ok || name == "" || tag == "" {
+		return "", fmt.Errorf("invalid riot id %q", riotID)
+	}
+	var account struct {
+		PUUID string `json:"puuid"`
+	}
+	path := fmt.Sprintf("/riot/account/v1/accounts/by-riot-id/%s/%s", url.PathEscape(name), url.PathEscape(tag))
+	if err := c.get(RiotRegion, path, &account); err != nil {
+		return "", err
+	}
+	return account.PUUID, nil
+}
+
+// fetchRank looks up the solo queue rank of a PUUID, e.g. "Gold II".
+func (c *riotClient) fetchRank(puuid string) (string, error) {
+	var entries []struct {
+		QueueType string `json:"queueType"`
+		Tier      string `json:"tier"`
+		Rank      string `json:"rank"`
+	}
+	if err := c.get(RiotPlatform, "/lol/league/v4/entries/by-puuid/"+url.PathEscape(puuid), &entries); err != nil {
+		return "", err
+	}
+	for _, e := range entries {
+		if e.QueueType == "RANKED_SOLO_5x5" && e.Tier != "" {
+			tier := strings.ToUpper(e.Tier[:1]) + strings.ToLower(e.Tier[1:])
+			return tier + " " + e.Rank, nil
+		}
+	}
+	return "Unranked", nil
+}
+
+// rank returns the cached rank of a PUUID and whether it needs fetching.
+func (c *riotClient) rank(puuid string) (string, bool) {
+	c.Lock()
+	defer c.Unlock()
+
+	cached, ok := c.ranks[puuid]
+	return cached.rank, !ok || time.Since(cached.fetched) > rankTTL
+}
+
+// refresh fetches a rank in the background, calling done once it is
+// cached. Concurrent refreshes of the same PUUID are dropped.
+func (c *riotClient) refresh(puuid string, done func()) {
+	c.Lock()
+	if c.inflight[puuid] {
+		c.Unlock()
+		return
+	}
+	c.inflight[puuid] = true
+	c.Unlock()
+
+	go func() {
+		rank, err := c.fetchRank(puuid)
+
+		c.Lock()
+		delete(c.inflight, puuid)
+		if err == nil {
+			c.ranks[puuid] = cachedRank{rank: rank, fetched: time.Now()}
+		}
+		c.Unlock()
+
+		if err != nil {
+			slog.Warn("error fetching rank", "err", err, "puuid", puuid)
+			return
+		}
+		done()
+	}()
+}
+
+// sweep drops ranks that haven't been fetched for a while.
+func (c *riotClient) sweep() {
+	c.Lock()
+	defer c.Unlock()
+
+	for puuid, cached := range c.ranks {
+		if time.Since(cached.fetched) > rankKeep {
+			delete(c.ranks, puuid)
+		}
+	}
+}
+
+// fetchRankLocked looks up a linked user's rank in the background if it
+// isn't cached, refreshing the queue once it arrives.
+//
+// lock must be held
+func (q *queueState) fetchRankLocked(s *discordgo.Session, userID string) {
+	link, ok := q.riotLinks[userID]
+	if RiotAPIKey == "" || !ok {
+		return
+	}
+	if _, stale := riot.rank(link.PUUID); !stale {
+		return
+	}
+	riot.refresh(link.PUUID, func() {
+		q.Lock()
+		defer q.Unlock()
+
+		if q.positionLocked(userID) > 0 {
+			q.scheduleRefreshLocked(s)
+		}
+	})
+}
+
+// rankTextLocked renders a queued user's cached rank for the queue embed, or
+// "" if there is none.
+//
+// lock must be held
+func (q *queueState) rankTextLocked(userID string) string {
+	link, ok := q.riotLinks[userID]
+	if RiotAPIKey == "" || !ok {
+		return ""
+	}
+	if rank, _ := riot.rank(link.PUUID); rank != "" {
+		return " · " + rank
+	}
+	return ""
+}
+
+func (q *queueState) handleLinkCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if RiotAPIKey == "" {
+		respondEphemeral(s, i, tr("Riot accounts can't be linked on this server."))
+		return
+	}
+	var riotID string
+	if opts := i.ApplicationCommandData().Options; len(opts) > 0 {
+		riotID = strings.TrimSpace(opts[0].StringValue())
+	}
+
+	if riotID == "" {
+		q.Lock()
+		delete(q.riotLinks, i.Member.User.ID)
+		q.Unlock()
+		respondEphemeral(s, i, tr("Riot account unlinked."))
+		return
+	}
+
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseDeferredChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{Flags: discordgo.MessageFlagsEphemeral},
+	})
+	puuid, err := riot.account(riotID)
+	if err != nil {
+		interactionLogger(i).Warn("error looking up riot id", "err", err, "riot_id", riotID)
+		followupEphemeral(s, i, tr("Couldn't find the Riot ID `%s`. Use the Name#TAG format.", riotID))
+		return
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	q.riotLinks[i.Member.User.ID] = riotLink{RiotID: riotID, PUUID: puuid}
+	q.fetchRankLocked(s, i.Member.User.ID)
+	followupEphemeral(s, i, tr("Linked Riot ID `%s`. Your rank will be shown in the queue.", riotID))
+}
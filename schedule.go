@@ -0,0 +1,109 @@
+package main
+
+import (
+	"fmt"
+	"log/slog"
+	"os"
+	"time"
+	// Embedded so STANDBY_TIMEZONE works on hosts without a zoneinfo
+	// database.
+	_ "time/tzdata"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// Timezone is the guild's timezone, which /standby at: times are read in.
+var Timezone = loadTimezone(os.Getenv("STANDBY_TIMEZONE"))
+
+func loadTimezone(name string) *time.Location {
+	if name == "" {
+		return time.UTC
+	}
+	loc, err := time.LoadLocation(name)
+	if err != nil {
+		panic(fmt.Sprintf("invalid STANDBY_TIMEZONE: %v", err))
+	}
+	return loc
+}
+
+// parseOpenTime reads a wall clock time like "20:30" in Timezone as its next
+// occurrence after now.
+func parseOpenTime(v string, now time.Time) (time.Time, error) {
+	clock, err := time.Parse("15:04", v)
+	if err != nil {
+		return time.Time{}, err
+	}
+	now = now.In(Timezone)
+	t := time.Date(now.Year(), now.Month(), now.Day(), clock.Hour(), clock.Minute(), 0, 0, Timezone)
+	if !t.After(now) {
+		t = t.AddDate(0, 0, 1)
+	}
+	return t, nil
+}
+
+// scheduleOpenLocked arranges for a queue with preset p and game gameKey to
+// open at a later time, on behalf of the user running /standby at:. It
+// replaces any queue already scheduled, saying so.
+//
+// lock must be held
+func (q *queueState) scheduleOpenLocked(s *discordgo.Session, i *discordgo.InteractionCreate, at string, thread bool, p preset, gameKey string) {
+	openAt, err := parseOpenTime(at, time.Now())
+	if err != nil {
+		respondEphemeral(s, i, tr("Use a 24-hour time like 20:30 (%s).", Timezone))
+		return
+	}
+	var replaced string
+	if q.scheduledOpen != nil {
+		q.scheduledOpen.Stop()
+		replaced = tr(" This replaces the queue that was scheduled for <t:%d:t>.", q.scheduledAt.Unix())
+	}
+
+	creatorID := i.Member.User.ID
+	var t *time.Timer
+	t = time.AfterFunc(time.Until(openAt), func() {
+		q.Lock()
+		defer q.Unlock()
+
+		if q.scheduledOpen != t {
+			return
+		}
+		q.scheduledOpen = nil
+		if q.currentMsgID != "" {
+			return
+		}
+		q.preset = p
+		q.game = gameKey
+		if thread {
+			if err := q.startThreadLocked(s); err != nil {
+				slog.Error("error starting scheduled queue thread", "err", err)
+				return
+			}
+		}
+		if err := q.openQueueLocked(s); err != nil {
+			slog.Error("error opening scheduled queue", "err", err)
+			q.threadID = ""
+			return
+		}
+		q.creatorID = creatorID
+	})
+	q.scheduledOpen = t
+	q.scheduledAt = openAt
+	q.scheduledBy = creatorID
+
+	respondEphemeral(s, i, tr("Queue will open <t:%d:t> (<t:%d:R>). Use /standby-close to cancel.", openAt.Unix(), openAt.Unix())+replaced)
+}
+
+// cancelScheduledOpenLocked answers /standby-close while no queue is open
+// but one is scheduled, cancelling it for whoever scheduled it or a
+// moderator.
+//
+// lock must be held
+func (q *queueState) cancelScheduledOpenLocked(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if q.scheduledBy != i.Member.User.ID && !q.canModerateLocked(s, i) {
+		respondEphemeral(s, i, tr("Only the queue creator, moderators or admins can do that."))
+		return
+	}
+	q.scheduledOpen.Stop()
+	q.scheduledOpen = nil
+	respondEphemeral(s, i, tr("Cancelled the queue scheduled for <t:%d:t>.", q.scheduledAt.Unix()))
+}
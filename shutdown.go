@@ -0,0 +1,225 @@
+package main
+
+import (
+	"fmt"
+	"log/slog"
+	"regexp"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// pausedText opens the paused embed. It is left untranslated since
+// restoreQueueLocked finds the paused message by it.
+const pausedText = "Bot restarting, queue preserved."
+
+var (
+	mentionRe = regexp.MustCompile(`<@(\d+)>`)
+	presetRe  = pausedLineRe("Preset: %s\n", `(.+)`)
+	gameRe    = pausedLineRe("Game: %s\n", `(.+)`)
+	pingRe    = pausedLineRe("Ready ping: %s\n", `(\d+)`)
+)
+
+// pausedLineRe matches a line of the paused embed written with the
+// translated format, capturing its argument with arg.
+func pausedLineRe(format, arg string) *regexp.Regexp {
+	before, after, _ := strings.Cut(tr(format, "\x00"), "\x00")
+	return regexp.MustCompile(`(?m)^` + regexp.QuoteMeta(before) + arg + regexp.QuoteMeta(strings.TrimSuffix(after, "\n")) + `$`)
+}
+
+// pauseLocked edits the queue message into a paused state with disabled
+// buttons before the bot exits. Queued users are always listed as mentions
+// so restoreQueueLocked can rebuild the queue from the message, along with
+// the ready ping so it is kept rather than sent again. Promotion offers and
+// an unfinished map veto can't be carried over, so they are withdrawn.
+//
+// lock must be held
+func (q *queueState) pauseLocked(s *discordgo.Session) {
+	if q.currentMsgID == "" {
+		return
+	}
+	for userID := range q.offers {
+		q.cancelOfferLocked(s, userID)
+	}
+	q.endVetoLocked(s)
+
+	var sb strings.Builder
+	sb.WriteString(pausedText + "\n")
+	sb.WriteString(tr("Preset: %s\n", q.preset.Name))
+	if q.game != "" {
+		sb.WriteString(tr("Game: %s\n", q.game))
+	}
+	if q.notifyMsgID != "" {
+		sb.WriteString(tr("Ready ping: %s\n", q.notifyMsgID))
+	}
+	sb.WriteString(tr("### Queued users (%d):\n", len(q.users)))
+	for _, user := range q.users {
+		sb.WriteString(fmt.Sprintf("<@%s>\n", user.ID))
+	}
+
+	_, err := editMessage(s, &discordgo.MessageEdit{
+		ID:      q.currentMsgID,
+		Channel: q.channelLocked(),
+		Embeds: []*discordgo.MessageEmbed{
+			{
+				Type:        discordgo.EmbedTypeRich,
+				Title:       queueTitle,
+				Color:       0x808080,
+				Description: sb.String(),
+				Timestamp:   q.startTime.Format(time.RFC3339),
+			},
+		},
+		Components: []discordgo.MessageComponent{
+			discordgo.ActionsRow{
+				Components: []discordgo.MessageComponent{
+					discordgo.Button{
+						Label:    tr("Join"),
+						Style:    discordgo.PrimaryButton,
+						CustomID: "join_queue",
+						Disabled: true,
+					},
+					discordgo.Button{
+						Label:    tr("Leave"),
+						Style:    discordgo.DangerButton,
+						CustomID: "leave_queue",
+						Disabled: true,
+					},
+					discordgo.Button{
+						Label:    tr("Close"),
+						Style:    discordgo.SecondaryButton,
+						CustomID: "close_queue",
+						Disabled: true,
+					},
+				},
+			},
+		},
+	})
+	if err != nil {
+		slog.Error("error editing message pausing queue", "err", err, "message", q.currentMsgID)
+	}
+}
+
+// restoreQueueLocked looks for a queue message paused by a previous run,
+// in ChannelID or one of the bot's active queue threads, and makes it the
+// active queue again. Older paused messages left behind by runs that never
+// restarted cleanly are closed so their buttons don't linger.
+//
+// lock must be held
+func (q *queueState) restoreQueueLocked(s *discordgo.Session) {
+	channels := []string{ChannelID}
+	threads, err := s.GuildThreadsActive(GuildID)
+	if err != nil {
+		slog.Error("error fetching threads to restore queue", "err", err)
+	} else {
+		for _, th := range threads.Threads {
+			if th.ParentID == ChannelID && th.OwnerID == AppID {
+				channels = append(channels, th.ID)
+			}
+		}
+	}
+
+	var paused []*discordgo.Message
+	for _, channelID := range channels {
+		msgs, err := s.ChannelMessages(channelID, 50, "", "", "")
+		if err != nil {
+			slog.Error("error fetching messages to restore queue", "err", err, "channel", channelID)
+			continue
+		}
+		for _, m := range msgs {
+			if m.Author != nil && m.Author.ID == AppID && len(m.Embeds) > 0 &&
+				m.Embeds[0].Title == queueTitle && strings.HasPrefix(m.Embeds[0].Description, pausedText) {
+				m.ChannelID = channelID
+				paused = append(paused, m)
+			}
+		}
+	}
+	if len(paused) == 0 {
+		return
+	}
+	slices.SortFunc(paused, func(a, b *discordgo.Message) int {
+		return b.Timestamp.Compare(a.Timestamp)
+	})
+	for _, m := range paused[1:] {
+		closePausedMessage(s, m)
+	}
+
+	latest := paused[0]
+	embed := latest.Embeds[0]
+	var users []*discordgo.User
+	for _, match := range mentionRe.FindAllStringSubmatch(embed.Description, -1) {
+		user, err := s.User(match[1])
+		if err != nil {
+			slog.Error("error fetching user to restore queue", "err", err, "user", match[1])
+			continue
+		}
+		users = append(users, user)
+	}
+	if len(users) == 0 {
+		if closePausedMessage(s, latest) && latest.ChannelID == ChannelID {
+			q.closedMsgID = latest.ID
+		}
+		return
+	}
+
+	startTime, err := time.Parse(time.RFC3339, embed.Timestamp)
+	if err != nil {
+		startTime = time.Now()
+	}
+
+	q.currentMsgID = latest.ID
+	if latest.ChannelID != ChannelID {
+		q.threadID = latest.ChannelID
+	}
+	q.preset = q.presetLocked(defaultPreset)
+	if match := presetRe.FindStringSubmatch(embed.Description); match != nil {
+		q.preset = q.presetLocked(match[1])
+	}
+	q.game = ""
+	if match := gameRe.FindStringSubmatch(embed.Description); match != nil {
+		q.game = match[1]
+	}
+	q.users = users
+	q.startTime = startTime
+	if match := pingRe.FindStringSubmatch(embed.Description); match != nil {
+		q.notifyMsgID = match[1]
+		q.lastStack = append([]*discordgo.User(nil), users[:min(len(users), q.preset.Size)]...)
+	}
+	for _, user := range users {
+		q.tagRegularLocked(s, user)
+		q.fetchRankLocked(s, user.ID)
+		// Join times aren't kept across restarts.
+		q.joinedAt[user.ID] = startTime
+	}
+	q.armReservationLocked(s)
+	q.touchIdleLocked(s)
+	if err := q.editQueueMessageLocked(s); err != nil {
+		// The message can't be brought back, e.g. it was deleted while the
+		// bot was down. Start from a clean slate rather than tracking it.
+		slog.Error("error editing message restoring queue", "err", err, "message", latest.ID)
+		q.currentMsgID = ""
+		q.threadID = ""
+		q.notifyMsgID = ""
+		q.lastStack = nil
+		q.users = nil
+		q.stopReservationLocked()
+		q.stopIdleLocked(s)
+		return
+	}
+	slog.Info("restored queue", "message", latest.ID, "users", len(users))
+}
+
+// closePausedMessage closes a paused queue message that won't be restored,
+// archiving its thread if it was in one, and reports whether it succeeded.
+func closePausedMessage(s *discordgo.Session, m *discordgo.Message) bool {
+	if m.ChannelID != ChannelID {
+		closeThread(s, m.ChannelID, m.ID)
+		return true
+	}
+	if err := editClosedMessage(s, m.ID, 0); err != nil {
+		slog.Error("error closing paused message", "err", err, "message", m.ID)
+		return false
+	}
+	return true
+}
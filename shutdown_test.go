@@ -0,0 +1,86 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+func TestRestoreThreadQueue(t *testing.T) {
+	oldChannel, oldGuild, oldApp := ChannelID, GuildID, AppID
+	t.Cleanup(func() { ChannelID, GuildID, AppID = oldChannel, oldGuild, oldApp })
+	ChannelID, GuildID, AppID = "chan", "guild", "app"
+
+	s, fake := newFakeSession(t)
+	q := newQueueState()
+
+	var sb strings.Builder
+	sb.WriteString(pausedText + "\n")
+	sb.WriteString(fmt.Sprintf("Preset: %s\n", defaultPreset))
+	sb.WriteString("Ready ping: ping\n")
+	sb.WriteString("### Queued users (5):\n")
+	fake.responses = map[string]string{
+		"GET /guilds/guild/threads/active": `{"threads":[{"id":"thread","parent_id":"chan","owner_id":"app","type":11}]}`,
+	}
+	for n := 1; n <= 5; n++ {
+		sb.WriteString(fmt.Sprintf("<@u%d>\n", n))
+		fake.responses[fmt.Sprintf("GET /users/u%d", n)] = fmt.Sprintf(`{"id":"u%d","username":"user%d"}`, n, n)
+	}
+
+	pausedMessage := func(id string, at time.Time) *discordgo.Message {
+		return &discordgo.Message{
+			ID:        id,
+			Author:    &discordgo.User{ID: AppID},
+			Timestamp: at,
+			Embeds: []*discordgo.MessageEmbed{
+				{Title: queueTitle, Description: sb.String()},
+			},
+		}
+	}
+	messages := func(msgs ...*discordgo.Message) string {
+		b, err := json.Marshal(msgs)
+		if err != nil {
+			t.Fatal(err)
+		}
+		return string(b)
+	}
+	now := time.Now()
+	fake.responses["GET /channels/chan/messages"] = messages(pausedMessage("old", now.Add(-time.Hour)))
+	fake.responses["GET /channels/thread/messages"] = messages(pausedMessage("paused", now))
+
+	q.Lock()
+	defer q.Unlock()
+	q.restoreQueueLocked(s)
+
+	if q.currentMsgID != "paused" || q.threadID != "thread" {
+		t.Fatalf("restored message %q in thread %q, want %q in %q", q.currentMsgID, q.threadID, "paused", "thread")
+	}
+	if got := userIDs(q.users); len(got) != 5 || got[0] != "u1" {
+		t.Errorf("users = %q, want u1 through u5", got)
+	}
+	if q.notifyMsgID != "ping" {
+		t.Errorf("notifyMsgID = %q, want %q", q.notifyMsgID, "ping")
+	}
+	if got := fake.count(http.MethodPatch, "/channels/thread/messages/paused"); got != 1 {
+		t.Errorf("edited the restored message %d times, want 1", got)
+	}
+	if got := fake.count(http.MethodPatch, "/channels/chan/messages/old"); got != 1 {
+		t.Errorf("edited the stale paused message %d times, want 1", got)
+	}
+
+	// The stack is still full, so the kept ping stands in for a new one.
+	if err := q.refreshLocked(s); err != nil {
+		t.Fatalf("refreshLocked() = %v", err)
+	}
+	if got := fake.count(http.MethodPost, "/channels/thread/messages"); got != 0 {
+		t.Errorf("sent %d messages after restoring, want 0", got)
+	}
+	if q.notifyMsgID != "ping" {
+		t.Errorf("notifyMsgID after refresh = %q, want %q", q.notifyMsgID, "ping")
+	}
+}
@@ -0,0 +1,58 @@
+package main
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// simulatedPrefix marks the IDs of fake users added by /standby-simulate.
+// Real Discord IDs are numeric so they can never collide.
+const simulatedPrefix = "sim-"
+
+func isSimulated(userID string) bool {
+	return strings.HasPrefix(userID, simulatedPrefix)
+}
+
+// mention pings a user, or just names them if they are simulated.
+func mention(user *discordgo.User) string {
+	if isSimulated(user.ID) {
+		return fmt.Sprintf("*%s*", user.Username)
+	}
+	return fmt.Sprintf("<@%s>", user.ID)
+}
+
+func (q *queueState) handleSimulateCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+	n := int(i.ApplicationCommandData().Options[0].IntValue())
+
+	q.Lock()
+	defer q.Unlock()
+
+	if q.currentMsgID == "" {
+		if err := q.openQueueLocked(s); err != nil {
+			interactionLogger(i).Error("error opening queue for simulation", "err", err)
+			respondEphemeral(s, i, tr("Couldn't open a queue."))
+			return
+		}
+	}
+
+	for j := 0; j < n; j++ {
+		q.nextSimulated++
+		user := &discordgo.User{
+			ID:       fmt.Sprintf("%s%d", simulatedPrefix, q.nextSimulated),
+			Username: fmt.Sprintf("Simulated user %d", q.nextSimulated),
+		}
+		q.addUserLocked(s, user)
+		q.recordActionLocked(user, actionJoin)
+	}
+	if err := q.refreshLocked(s); err != nil {
+		interactionLogger(i).Error("error editing message for simulation", "err", err)
+	}
+
+	respondEphemeral(s, i, tr("Added %d simulated users.", n))
+}
@@ -0,0 +1,48 @@
+package main
+
+import (
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// toggleSpectatorLocked adds the user to the spectators, or removes them if
+// they were already watching, and reports whether they are now spectating.
+//
+// lock must be held
+func (q *queueState) toggleSpectatorLocked(user *discordgo.User) bool {
+	if q.removeSpectatorLocked(user.ID) {
+		return false
+	}
+	q.spectators = append(q.spectators, user)
+	return true
+}
+
+// removeSpectatorLocked reports whether the user was spectating.
+//
+// lock must be held
+func (q *queueState) removeSpectatorLocked(userID string) bool {
+	for idx, user := range q.spectators {
+		if user.ID == userID {
+			q.spectators = append(q.spectators[:idx], q.spectators[idx+1:]...)
+			return true
+		}
+	}
+	return false
+}
+
+// spectatorsTextLocked lists the users watching the queue. They don't take
+// a slot in the stack.
+//
+// lock must be held
+func (q *queueState) spectatorsTextLocked() string {
+	if len(q.spectators) == 0 {
+		return ""
+	}
+	var sb strings.Builder
+	sb.WriteString(tr("### Spectators (%d):\n", len(q.spectators)))
+	for _, user := range q.spectators {
+		sb.WriteString(q.mentionLocked(user) + "\n")
+	}
+	return sb.String()
+}
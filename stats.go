@@ -0,0 +1,66 @@
+package main
+
+import (
+	"time"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// userStats are a user's queue counters for /stats.
+type userStats struct {
+	Queued int `json:"queued"`
+	Filled int `json:"filled"`
+	// NoShows counts kicks from a stack that had already filled.
+	NoShows int `json:"no_shows"`
+	// TotalWait is the time spent queued before filled stacks.
+	TotalWait time.Duration `json:"total_wait"`
+}
+
+// lock must be held
+func (q *queueState) statsLocked(userID string) *userStats {
+	st, ok := q.stats[userID]
+	if !ok {
+		st = &userStats{}
+		q.stats[userID] = st
+	}
+	return st
+}
+
+// recordFilledLocked counts a filled stack and its wait for each user in it.
+//
+// lock must be held
+func (q *queueState) recordFilledLocked(stack []*discordgo.User) {
+	now := time.Now()
+	for _, user := range stack {
+		st := q.statsLocked(user.ID)
+		st.Filled++
+		if joined, ok := q.joinedAt[user.ID]; ok {
+			st.TotalWait += now.Sub(joined)
+		}
+	}
+}
+
+// recordKickLocked counts a no-show if the kicked user was part of a stack
+// that had already filled.
+//
+// lock must be held
+func (q *queueState) recordKickLocked(userID string) {
+	if q.notifyMsgID == "" {
+		return
+	}
+	for _, user := range q.lastStack {
+		if user.ID == userID {
+			q.statsLocked(userID).NoShows++
+			return
+		}
+	}
+}
+
+// averageWait returns the mean wait before a filled stack, or 0 if the user
+// has never filled one.
+func (st *userStats) averageWait() time.Duration {
+	if st.Filled == 0 {
+		return 0
+	}
+	return (st.TotalWait / time.Duration(st.Filled)).Round(time.Second)
+}
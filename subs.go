@@ -0,0 +1,93 @@
+package main
+
+import (
+	"log/slog"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// toggleSubLocked marks the user as willing to substitute, or unmarks them
+// if they already were, and reports whether they are now a sub.
+//
+// lock must be held
+func (q *queueState) toggleSubLocked(user *discordgo.User) bool {
+	if q.removeSubLocked(user.ID) {
+		return false
+	}
+	q.subs = append(q.subs, user)
+	return true
+}
+
+// removeSubLocked reports whether the user was a sub.
+//
+// lock must be held
+func (q *queueState) removeSubLocked(userID string) bool {
+	for idx, user := range q.subs {
+		if user.ID == userID {
+			q.subs = append(q.subs[:idx], q.subs[idx+1:]...)
+			return true
+		}
+	}
+	return false
+}
+
+// callSubsLocked pings the subs when a player drops out of a stack that
+// already got its ready ping, before anyone on the waitlist is told.
+//
+// lock must be held
+func (q *queueState) callSubsLocked(s *discordgo.Session, dropped *discordgo.User) {
+	if q.notifyMsgID == "" || len(q.subs) == 0 {
+		return
+	}
+	q.subCalls++
+
+	mentions := make([]string, len(q.subs))
+	for idx, user := range q.subs {
+		mentions[idx] = mention(user)
+	}
+	content := tr("%s dropped out of the stack. Subs, click Join to take their spot: %s", q.nameLocked(dropped), strings.Join(mentions, " "))
+	if _, err := sendMessage(s, q.channelLocked(), &discordgo.MessageSend{Content: content}); err != nil {
+		slog.Error("error sending sub call", "err", err)
+	}
+}
+
+// seatSubLocked moves a sub who was just added to the queue into the stack
+// ahead of the waitlist if a sub call is open, withdrawing any offer made to
+// the waitlister they push back. Joining always clears the user's sub mark.
+//
+// lock must be held
+func (q *queueState) seatSubLocked(s *discordgo.Session, user *discordgo.User) {
+	if !q.removeSubLocked(user.ID) || q.subCalls == 0 {
+		return
+	}
+	q.subCalls--
+	if len(q.subs) == 0 {
+		q.subCalls = 0
+	}
+
+	slot := q.preset.Size - 1
+	last := len(q.users) - 1
+	if last <= slot {
+		return
+	}
+	copy(q.users[slot+1:last+1], q.users[slot:last])
+	q.users[slot] = user
+	q.cancelOfferLocked(s, q.users[slot+1].ID)
+}
+
+// subsTextLocked lists the users standing by to substitute. They aren't on
+// the waitlist.
+//
+// lock must be held
+func (q *queueState) subsTextLocked() string {
+	if len(q.subs) == 0 {
+		return ""
+	}
+	var sb strings.Builder
+	sb.WriteString(tr("### Subs (%d):\n", len(q.subs)))
+	for _, user := range q.subs {
+		sb.WriteString(q.mentionLocked(user) + "\n")
+	}
+	return sb.String()
+}
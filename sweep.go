@@ -0,0 +1,54 @@
+package main
+
+import (
+	"time"
+)
+
+// sweepInterval is how often expired per-user bookkeeping is dropped.
+const sweepInterval = 10 * time.Minute
+
+// sweep periodically drops per-user entries that no longer affect anything,
+// so users who stop playing don't accumulate state forever.
+func (q *queueState) sweep() {
+	for range time.Tick(sweepInterval) {
+		q.Lock()
+		q.sweepLocked()
+		q.Unlock()
+
+		commandLimiter.sweep()
+		riot.sweep()
+	}
+}
+
+// lock must be held
+func (q *queueState) sweepLocked() {
+	for userID, last := range q.lastClick {
+		if time.Since(last) >= ButtonCooldown {
+			delete(q.lastClick, userID)
+		}
+	}
+	for userID, last := range q.lastFeedback {
+		if time.Since(last) >= feedbackCooldown {
+			delete(q.lastFeedback, userID)
+		}
+	}
+	for userID := range q.games {
+		q.gamesTonightLocked(userID)
+	}
+	for userID := range q.mutedUntil {
+		q.mutedLocked(userID)
+	}
+}
+
+// sweep drops buckets that have refilled completely, which behave the same
+// as missing ones.
+func (r *rateLimiter) sweep() {
+	r.Lock()
+	defer r.Unlock()
+
+	for key, b := range r.buckets {
+		if time.Since(b.last) >= r.window {
+			delete(r.buckets, key)
+		}
+	}
+}
@@ -0,0 +1,91 @@
+package main
+
+import (
+	"fmt"
+	"math/rand"
+	"os"
+	"sort"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// TeamSplit selects how "Split Teams" divides players: "rating" balances by
+// Elo rating, anything else splits randomly. Presets can override it.
+var TeamSplit = os.Getenv("STANDBY_TEAM_SPLIT")
+
+// splitTeamsLocked divides the first two teams' worth of queued users into
+// two teams.
+//
+// lock must be held
+func (q *queueState) splitTeamsLocked() (a, b []*discordgo.User) {
+	size := q.preset.TeamSize
+	players := append([]*discordgo.User(nil), q.users[:2*size]...)
+
+	if q.preset.TeamSplit != "rating" {
+		rand.Shuffle(len(players), func(x, y int) {
+			players[x], players[y] = players[y], players[x]
+		})
+		return players[:size], players[size:]
+	}
+
+	// Hand out players strongest first, each to the team with the lower
+	// total that still has room.
+	sort.SliceStable(players, func(x, y int) bool {
+		return q.ratingLocked(players[x].ID) > q.ratingLocked(players[y].ID)
+	})
+	var ratingA, ratingB float64
+	for _, p := range players {
+		r := q.ratingLocked(p.ID)
+		if len(b) == size || (len(a) < size && ratingA <= ratingB) {
+			a = append(a, p)
+			ratingA += r
+		} else {
+			b = append(b, p)
+			ratingB += r
+		}
+	}
+	return a, b
+}
+
+// lock must be held
+func (q *queueState) teamFieldLocked(name string, team []*discordgo.User) *discordgo.MessageEmbedField {
+	lines := make([]string, len(team))
+	for idx, user := range team {
+		lines[idx] = q.mentionLocked(user)
+		if q.preset.TeamSplit == "rating" {
+			lines[idx] += fmt.Sprintf(" (%.0f)", q.ratingLocked(user.ID))
+		}
+	}
+	return &discordgo.MessageEmbedField{
+		Name:   name,
+		Value:  strings.Join(lines, "\n"),
+		Inline: true,
+	}
+}
+
+// lock must be held
+func (q *queueState) handleSplitTeamsLocked(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if len(q.users) < 2*q.preset.TeamSize {
+		respondEphemeral(s, i, tr("Need %d players to split teams.", 2*q.preset.TeamSize))
+		return
+	}
+
+	a, b := q.splitTeamsLocked()
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseChannelMessageWithSource,
+		Data: &discordgo.InteractionResponseData{
+			Embeds: []*discordgo.MessageEmbed{
+				{
+					Type:  discordgo.EmbedTypeRich,
+					Title: tr("Teams"),
+					Color: 0x0099FF,
+					Fields: []*discordgo.MessageEmbedField{
+						q.teamFieldLocked(tr("Team %d", 1), a),
+						q.teamFieldLocked(tr("Team %d", 2), b),
+					},
+				},
+			},
+		},
+	})
+}
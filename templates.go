@@ -0,0 +1,51 @@
+package main
+
+import (
+	"strconv"
+	"strings"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// Notification kinds that can be given a custom template.
+const (
+	templateReady   = "ready"
+	templateOneMore = "one-more"
+)
+
+// renderTemplate fills in a notification template's placeholders. name is
+// the queue's name, from queueNameLocked.
+func renderTemplate(tmpl, name string, needed int, mentions string) string {
+	return strings.NewReplacer(
+		"{count_needed}", strconv.Itoa(needed),
+		"{mentions}", mentions,
+		"{queue_name}", name,
+	).Replace(tmpl)
+}
+
+func (q *queueState) handleTemplateCommand(s *discordgo.Session, i *discordgo.InteractionCreate) {
+	if !isAdmin(s, i) {
+		respondEphemeral(s, i, tr("Only admins can use this command."))
+		return
+	}
+	var kind, text string
+	for _, opt := range i.ApplicationCommandData().Options {
+		switch opt.Name {
+		case "kind":
+			kind = opt.StringValue()
+		case "text":
+			text = strings.TrimSpace(opt.StringValue())
+		}
+	}
+
+	q.Lock()
+	defer q.Unlock()
+
+	if text == "" {
+		delete(q.templates, kind)
+		respondEphemeral(s, i, tr("The %s notification is back to the default text.", kind))
+		return
+	}
+	q.templates[kind] = text
+	respondEphemeral(s, i, tr("The %s notification will now read:\n%s", kind, renderTemplate(text, q.queueNameLocked(), 1, "@user")))
+}
@@ -0,0 +1,39 @@
+package main
+
+import "testing"
+
+func TestRenderTemplateQueueName(t *testing.T) {
+	tests := []struct {
+		name string
+		q    *queueState
+		want string
+	}{
+		{
+			name: "default",
+			q:    &queueState{preset: preset{Name: defaultPreset}},
+			want: queueTitle + " needs 1",
+		},
+		{
+			name: "game and preset",
+			q:    &queueState{preset: preset{Name: "trio"}, game: "valorant", knownGames: builtinGames()},
+			want: "Valorant Standby Queue (trio) needs 1",
+		},
+		{
+			name: "details title",
+			q: &queueState{
+				preset:     preset{Name: defaultPreset},
+				game:       "valorant",
+				knownGames: builtinGames(),
+				details:    queueDetails{Title: "Friday scrims"},
+			},
+			want: "Friday scrims needs 1",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := renderTemplate("{queue_name} needs {count_needed}", tt.q.queueNameLocked(), 1, ""); got != tt.want {
+				t.Errorf("renderTemplate() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,95 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// theme controls how the queue embed and ready notification look.
+type theme struct {
+	Color     int
+	Thumbnail string
+	// Ready opens the ready notification.
+	Ready string
+}
+
+var defaultTheme = theme{
+	Color: 0x0099FF,
+	Ready: "There are enough users for a game!",
+}
+
+// themes are the built-in themes that can be picked by name.
+var themes = map[string]theme{
+	"default": defaultTheme,
+	"halloween": {
+		Color: 0xFF7518,
+		Ready: "🎃 The stack has risen! Time for a game!",
+	},
+	"winter": {
+		Color: 0xA5F2F3,
+		Ready: "❄️ The stack is full, warm up for a game!",
+	},
+	"newyear": {
+		Color: 0xFFD700,
+		Ready: "🎉 New stack, new year! Time for a game!",
+	},
+}
+
+var (
+	// Theme names a theme to always use. Unset follows ThemeSchedule, and
+	// "default" opts out of seasonal themes.
+	Theme = os.Getenv("STANDBY_THEME")
+	// ThemeSchedule maps months to themes, e.g. "10:halloween,12:winter".
+	ThemeSchedule = parseThemeSchedule(os.Getenv("STANDBY_THEME_SCHEDULE"))
+	// ThemeColor and ThemeThumbnail override the resolved theme, for a
+	// custom look.
+	ThemeColor     = os.Getenv("STANDBY_THEME_COLOR")
+	ThemeThumbnail = os.Getenv("STANDBY_THEME_THUMBNAIL")
+)
+
+func parseThemeSchedule(v string) map[time.Month]string {
+	if v == "" {
+		return map[time.Month]string{
+			time.October:  "halloween",
+			time.December: "winter",
+			time.January:  "newyear",
+		}
+	}
+	schedule := map[time.Month]string{}
+	for _, entry := range splitList(v) {
+		month, name, ok := strings.Cut(entry, ":")
+		m, err := strconv.Atoi(month)
+		if !ok || err != nil || m < 1 || m > 12 {
+			panic(fmt.Sprintf("invalid STANDBY_THEME_SCHEDULE entry %q", entry))
+		}
+		if _, ok := themes[name]; !ok {
+			panic(fmt.Sprintf("unknown theme %q in STANDBY_THEME_SCHEDULE", name))
+		}
+		schedule[time.Month(m)] = name
+	}
+	return schedule
+}
+
+// currentTheme resolves the theme in effect at now.
+func currentTheme(now time.Time) theme {
+	name := Theme
+	if name == "" {
+		name = ThemeSchedule[now.Month()]
+	}
+	t, ok := themes[name]
+	if !ok {
+		t = defaultTheme
+	}
+	if ThemeColor != "" {
+		if c, err := strconv.ParseInt(strings.TrimPrefix(ThemeColor, "#"), 16, 32); err == nil {
+			t.Color = int(c)
+		}
+	}
+	if ThemeThumbnail != "" {
+		t.Thumbnail = ThemeThumbnail
+	}
+	return t
+}
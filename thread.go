@@ -0,0 +1,63 @@
+package main
+
+import (
+	"log/slog"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// threadArchiveMinutes is how long a queue thread may go quiet before
+// Discord archives it on its own.
+const threadArchiveMinutes = 1440
+
+// channelLocked returns the channel the current queue lives in: its thread,
+// if it was opened in one, or ChannelID.
+//
+// lock must be held
+func (q *queueState) channelLocked() string {
+	if q.threadID != "" {
+		return q.threadID
+	}
+	return ChannelID
+}
+
+// startThreadLocked creates a thread under ChannelID for the next queue to
+// open in.
+//
+// lock must be held
+func (q *queueState) startThreadLocked(s *discordgo.Session) error {
+	th, err := s.ThreadStart(ChannelID, queueTitle, discordgo.ChannelTypeGuildPublicThread, threadArchiveMinutes)
+	if err != nil {
+		return err
+	}
+	q.threadID = th.ID
+	return nil
+}
+
+// closeThread marks a thread queue's message closed and archives the thread.
+// Thread queues aren't reopened, so no Open button is left behind.
+func closeThread(s *discordgo.Session, threadID, msgID string) {
+	if _, err := editMessage(s, &discordgo.MessageEdit{
+		ID:      msgID,
+		Channel: threadID,
+		Embeds: []*discordgo.MessageEmbed{
+			{
+				Type:        discordgo.EmbedTypeRich,
+				Title:       queueTitle,
+				Color:       0x0099FF,
+				Description: tr("Queue is closed"),
+			},
+		},
+		Components: []discordgo.MessageComponent{},
+	}); err != nil {
+		slog.Error("error editing message closing thread queue", "err", err, "message", msgID)
+	}
+
+	archived := true
+	if _, err := s.ChannelEditComplex(threadID, &discordgo.ChannelEdit{
+		Archived: &archived,
+		Locked:   &archived,
+	}); err != nil {
+		slog.Error("error archiving queue thread", "err", err, "thread", threadID)
+	}
+}
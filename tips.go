@@ -0,0 +1,45 @@
+package main
+
+import "time"
+
+// onboardingTips is sent once to each user the first time they use the
+// queue buttons.
+func onboardingTips(size int) string {
+	return tr(`**New to the standby queue?**
+- The first %[1]d users make up the stack. Anyone after that is on the waitlist.
+- %[2]s
+- Once %[1]d users are queued, the stack is pinged in this channel. You're not pinged for joins or leaves.
+- Click Leave if you can no longer play so the waitlist can move up.`, size, promotionTip())
+}
+
+// promotionTip explains who takes a stack slot that opens up under
+// PromotionPolicy, and whether they have to accept it.
+func promotionTip() string {
+	var tip string
+	switch PromotionPolicy {
+	case promotePriority:
+		tip = tr("When someone in the stack leaves, the first waitlisted user with the priority role is promoted into their slot, or the longest waiting if nobody has it.")
+	case promoteLeastGames:
+		tip = tr("When someone in the stack leaves, the waitlisted user who has been in the fewest stacks tonight is promoted into their slot.")
+	case promoteRandom:
+		tip = tr("When someone in the stack leaves, a random waitlisted user is promoted into their slot.")
+	default:
+		tip = tr("When someone in the stack leaves, the longest waiting user is promoted into their slot.")
+	}
+	if PromotionTimeout > 0 {
+		tip += tr(" They have %s to accept the spot, or it goes to the next person.", PromotionTimeout.Round(time.Second))
+	}
+	return tip
+}
+
+// firstVisitLocked reports whether the user hasn't seen the onboarding tips
+// yet, and marks them as seen.
+//
+// lock must be held
+func (q *queueState) firstVisitLocked(userID string) bool {
+	if q.seenTips[userID] {
+		return false
+	}
+	q.seenTips[userID] = true
+	return true
+}
@@ -0,0 +1,20 @@
+package main
+
+import (
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestPromotionTip(t *testing.T) {
+	withPromotion(t, promoteRandom, nil)
+	if got := promotionTip(); !strings.Contains(got, "a random waitlisted user") || strings.Contains(got, "accept") {
+		t.Errorf("random without offers: promotionTip() = %q", got)
+	}
+
+	PromotionPolicy = promoteLeastGames
+	PromotionTimeout = 2 * time.Minute
+	if got := promotionTip(); !strings.Contains(got, "fewest stacks") || !strings.Contains(got, "2m0s to accept") {
+		t.Errorf("least-games with offers: promotionTip() = %q", got)
+	}
+}
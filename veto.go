@@ -0,0 +1,215 @@
+package main
+
+import (
+	"fmt"
+	"log/slog"
+	"os"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+// MapPool is the comma-separated list of maps to veto once the stack is
+// ready. Vetoes are skipped when fewer than two maps are configured.
+var MapPool = mapPoolEnv("STANDBY_MAP_POOL")
+
+// maxVetoMaps is how many map buttons fit on a message.
+const maxVetoMaps = 25
+
+// maxMapName is the longest map name that fits on a button.
+const maxMapName = 80
+
+const vetoPrefix = "veto:"
+
+func splitList(v string) []string {
+	var out []string
+	for _, item := range strings.Split(v, ",") {
+		if item = strings.TrimSpace(item); item != "" {
+			out = append(out, item)
+		}
+	}
+	return out
+}
+
+// mapPoolEnv reads the map pool from the environment. Each map becomes a
+// button keyed by its name, so a duplicate or overlong name would have
+// Discord reject the whole ready notification.
+func mapPoolEnv(key string) []string {
+	pool := splitList(os.Getenv(key))
+	seen := map[string]bool{}
+	for _, name := range pool {
+		if seen[name] {
+			panic(fmt.Sprintf("invalid %s: %q is listed twice", key, name))
+		}
+		if utf8.RuneCountInString(name) > maxMapName {
+			panic(fmt.Sprintf("invalid %s: %q is longer than %d characters", key, name, maxMapName))
+		}
+		seen[name] = true
+	}
+	return pool
+}
+
+// mapVeto has the ready stack take turns, in queue order, banning maps from
+// the pool until one remains. Turns follow whoever is in the stack at the
+// time, so a player who leaves is replaced by the one promoted after them.
+type mapVeto struct {
+	// content is the ready notification the veto is attached to.
+	content   string
+	remaining []string
+	bans      int
+	// turnID is the player the notification currently asks to ban, or ""
+	// if it asks nobody.
+	turnID string
+}
+
+func newMapVeto(content string) *mapVeto {
+	pool := MapPool
+	if len(pool) > maxVetoMaps {
+		pool = pool[:maxVetoMaps]
+	}
+	return &mapVeto{
+		content:   content,
+		remaining: append([]string(nil), pool...),
+	}
+}
+
+// vetoTurnLocked returns the stack player whose turn it is to ban, skipping
+// simulated users who can't click, or nil if nobody in the stack can.
+//
+// lock must be held
+func (q *queueState) vetoTurnLocked() *discordgo.User {
+	var players []*discordgo.User
+	for _, user := range q.users[:min(len(q.users), q.preset.Size)] {
+		if !isSimulated(user.ID) {
+			players = append(players, user)
+		}
+	}
+	if len(players) == 0 {
+		return nil
+	}
+	return players[q.veto.bans%len(players)]
+}
+
+// lock must be held
+func (q *queueState) vetoMessageLocked() (string, []discordgo.MessageComponent) {
+	v := q.veto
+	v.turnID = ""
+	if len(v.remaining) == 1 {
+		return tr("%s\n### Map: %s", v.content, v.remaining[0]), []discordgo.MessageComponent{lobbyCodeRow()}
+	}
+	turn := q.vetoTurnLocked()
+	if turn == nil {
+		// The veto resumes once a real player is promoted into the stack.
+		return v.content, []discordgo.MessageComponent{lobbyCodeRow()}
+	}
+	v.turnID = turn.ID
+
+	var rows []discordgo.MessageComponent
+	var buttons []discordgo.MessageComponent
+	for _, name := range v.remaining {
+		buttons = append(buttons, discordgo.Button{
+			Label:    name,
+			Style:    discordgo.SecondaryButton,
+			CustomID: vetoPrefix + name,
+		})
+		if len(buttons) == 5 {
+			rows = append(rows, discordgo.ActionsRow{Components: buttons})
+			buttons = nil
+		}
+	}
+	if len(buttons) > 0 {
+		rows = append(rows, discordgo.ActionsRow{Components: buttons})
+	}
+	// A message fits five rows; the lobby code button comes back once the
+	// veto is over if the maps use them all.
+	if len(rows) < 5 {
+		rows = append(rows, lobbyCodeRow())
+	}
+	return tr("%s\n### Map veto\n%s, ban a map.", v.content, mention(turn)), rows
+}
+
+// syncVetoLocked re-renders the ready ping if the stack changed whose turn
+// it is to ban, e.g. because the player on turn left.
+//
+// lock must be held
+func (q *queueState) syncVetoLocked(s *discordgo.Session) {
+	v := q.veto
+	if v == nil || len(v.remaining) == 1 || q.notifyMsgID == "" {
+		return
+	}
+	var turnID string
+	if turn := q.vetoTurnLocked(); turn != nil {
+		turnID = turn.ID
+	}
+	if turnID == v.turnID {
+		return
+	}
+	content, components := q.vetoMessageLocked()
+	if _, err := editMessage(s, &discordgo.MessageEdit{
+		ID:         q.notifyMsgID,
+		Channel:    q.channelLocked(),
+		Content:    &content,
+		Components: components,
+	}); err != nil {
+		slog.Error("error editing map veto turn", "err", err, "message", q.notifyMsgID)
+	}
+}
+
+// lock must be held
+func (q *queueState) handleVetoLocked(s *discordgo.Session, i *discordgo.InteractionCreate, name string) {
+	v := q.veto
+	if v == nil || i.Message.ID != q.notifyMsgID || len(v.remaining) == 1 {
+		respondEphemeral(s, i, tr("This map veto is over."))
+		return
+	}
+	player := q.vetoTurnLocked()
+	if player == nil {
+		respondEphemeral(s, i, tr("This map veto is over."))
+		return
+	}
+	if player.ID != i.Member.User.ID {
+		respondEphemeral(s, i, tr("It's %s's turn to ban.", q.nameLocked(player)))
+		return
+	}
+
+	for idx, m := range v.remaining {
+		if m == name {
+			v.remaining = append(v.remaining[:idx], v.remaining[idx+1:]...)
+			v.bans++
+			break
+		}
+	}
+
+	content, components := q.vetoMessageLocked()
+	s.InteractionRespond(i.Interaction, &discordgo.InteractionResponse{
+		Type: discordgo.InteractionResponseUpdateMessage,
+		Data: &discordgo.InteractionResponseData{
+			Content:    content,
+			Components: components,
+		},
+	})
+}
+
+// endVetoLocked drops an unfinished map veto, editing its buttons off the
+// ready ping and leaving the ping itself in place.
+//
+// lock must be held
+func (q *queueState) endVetoLocked(s *discordgo.Session) {
+	v := q.veto
+	if v == nil {
+		return
+	}
+	q.veto = nil
+	if len(v.remaining) == 1 || q.notifyMsgID == "" {
+		return
+	}
+	if _, err := editMessage(s, &discordgo.MessageEdit{
+		ID:         q.notifyMsgID,
+		Channel:    q.channelLocked(),
+		Content:    &v.content,
+		Components: []discordgo.MessageComponent{lobbyCodeRow()},
+	}); err != nil {
+		slog.Error("error ending map veto", "err", err, "message", q.notifyMsgID)
+	}
+}
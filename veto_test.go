@@ -0,0 +1,35 @@
+package main
+
+import (
+	"testing"
+
+	"github.com/bwmarrin/discordgo"
+)
+
+func TestVetoTurnLocked(t *testing.T) {
+	sim := &discordgo.User{ID: simulatedPrefix + "1", Username: "sim"}
+	q := &queueState{
+		preset: preset{Name: "trio", Size: 3},
+		users:  []*discordgo.User{alice, sim, bob, carol},
+		veto:   &mapVeto{remaining: []string{"a", "b", "c"}},
+	}
+
+	for bans, want := range []*discordgo.User{alice, bob, alice} {
+		q.veto.bans = bans
+		if got := q.vetoTurnLocked(); got != want {
+			t.Errorf("turn after %d bans = %v, want %s", bans, got, want.Username)
+		}
+	}
+
+	// bob leaves and carol is promoted into his slot.
+	q.users = []*discordgo.User{alice, sim, carol}
+	q.veto.bans = 1
+	if got := q.vetoTurnLocked(); got != carol {
+		t.Errorf("turn after bob left = %v, want carol", got)
+	}
+
+	q.users = []*discordgo.User{sim}
+	if got := q.vetoTurnLocked(); got != nil {
+		t.Errorf("turn with only simulated players = %v, want nil", got)
+	}
+}